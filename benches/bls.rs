@@ -0,0 +1,26 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use rand::thread_rng;
+use sp1_hash2curve::{sign, verify, verify_batch, BlsPrivateKey, BlsPublicKey};
+
+fn bench_verify(c: &mut Criterion) {
+    let mut rng = thread_rng();
+
+    let mut group = c.benchmark_group("bls_verify");
+    group.sample_size(10);
+    for k in [8, 32, 128] {
+        let msgs: Vec<Vec<u8>> = (0..k as u32).map(|i| i.to_le_bytes().to_vec()).collect();
+        let msgs: Vec<&[u8]> = msgs.iter().map(|m| m.as_slice()).collect();
+        let sks: Vec<BlsPrivateKey> = (0..k).map(|_| BlsPrivateKey::random(&mut rng)).collect();
+        let pks: Vec<BlsPublicKey> = sks.iter().map(BlsPublicKey::from_private).collect();
+        let sigs: Vec<_> = sks.iter().zip(&msgs).map(|(sk, msg)| sign(msg, sk)).collect();
+
+        group.bench_with_input(BenchmarkId::new("batch", k), &k, |b, _| b.iter(|| verify_batch(&msgs, &pks, &sigs)));
+        group.bench_with_input(BenchmarkId::new("individual", k), &k, |b, _| {
+            b.iter(|| msgs.iter().zip(&pks).zip(&sigs).all(|((msg, pk), sig)| verify(msg, pk, sig)))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_verify);
+criterion_main!(benches);
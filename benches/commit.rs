@@ -0,0 +1,32 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use rand::thread_rng;
+use sp1_hash2curve::{commit, commit_with_table, CommitmentKey, CommitmentParams, GeneratorTable};
+use substrate_bn::Fr;
+
+const DST: &[u8] = b"QUUX-V01-CS02-with-BN254G1_XMD:SHA-256_SVDW_RO_";
+
+fn bench_commit(c: &mut Criterion) {
+    let mut rng = thread_rng();
+    let params = CommitmentParams::new(DST).unwrap();
+
+    let mut group = c.benchmark_group("commit");
+    group.sample_size(10);
+    for n in [10, 100, 1000] {
+        let vs = (0..n).map(|_| Fr::random(&mut rng)).collect::<Vec<_>>();
+        let r = Fr::random(&mut rng);
+        let table = GeneratorTable::new(n, DST);
+        let key = CommitmentKey::new(n, DST).unwrap();
+
+        group.bench_with_input(BenchmarkId::new("commit", n), &vs, |b, vs| b.iter(|| commit(vs.iter().copied(), &params, r)));
+        group.bench_with_input(BenchmarkId::new("commit_with_table", n), &vs, |b, vs| b.iter(|| commit_with_table(vs, &table, r)));
+        group.bench_with_input(BenchmarkId::new("commitment_key", n), &vs, |b, vs| b.iter(|| key.commit(vs, r)));
+        // Rebuilding the key each time is what `commit` cost before generators were cached
+        group.bench_with_input(BenchmarkId::new("uncached", n), &vs, |b, vs| {
+            b.iter(|| CommitmentKey::new(vs.len(), DST).unwrap().commit(vs, r))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_commit);
+criterion_main!(benches);
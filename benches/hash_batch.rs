@@ -0,0 +1,16 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use sp1_hash2curve::HashToCurve;
+use substrate_bn::AffineG1;
+
+fn bench_hash_batch(c: &mut Criterion) {
+    let msgs: Vec<Vec<u8>> = (0..1000u32).map(|i| i.to_le_bytes().to_vec()).collect();
+    let msgs: Vec<&[u8]> = msgs.iter().map(|m| m.as_slice()).collect();
+
+    let mut group = c.benchmark_group("hash_batch/g1/1000");
+    group.bench_function("sequential", |b| b.iter(|| msgs.iter().map(|msg| AffineG1::hash(msg, AffineG1::DST)).collect::<Vec<_>>()));
+    group.bench_function("parallel", |b| b.iter(|| AffineG1::hash_batch_parallel(&msgs, AffineG1::DST)));
+    group.finish();
+}
+
+criterion_group!(benches, bench_hash_batch);
+criterion_main!(benches);
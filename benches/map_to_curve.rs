@@ -0,0 +1,44 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use sp1_hash2curve::{batch_map_to_curve_g1, HashToCurve, HashToField};
+use substrate_bn::{AffineG1, AffineG2, Fq, Fq2};
+
+// Constants are parsed on first use, so every iteration after warm-up measures only the field work
+fn bench_map_to_curve(c: &mut Criterion) {
+    let u1 = Fq::hash_to_field(b"abc", AffineG1::DST, 1).unwrap()[0];
+    let u2 = Fq2::hash_to_field(b"abc", AffineG2::DST, 1).unwrap()[0];
+
+    let mut group = c.benchmark_group("map_to_curve");
+    group.bench_function("g1", |b| b.iter(|| AffineG1::map_to_curve(black_box(u1)).unwrap()));
+    group.bench_function("g2", |b| b.iter(|| AffineG2::map_to_curve(black_box(u2)).unwrap()));
+    group.finish();
+}
+
+// One shared inversion against one per element
+fn bench_batch_map_to_curve(c: &mut Criterion) {
+    let mut group = c.benchmark_group("batch_map_to_curve/g1");
+    for n in [8, 64, 256] {
+        let us = Fq::hash_to_field(b"abc", AffineG1::DST, n).unwrap();
+        group.bench_with_input(BenchmarkId::new("batch", n), &n, |b, _| b.iter(|| batch_map_to_curve_g1(black_box(&us)).unwrap()));
+        group.bench_with_input(BenchmarkId::new("individual", n), &n, |b, _| {
+            b.iter(|| us.iter().map(|&u| AffineG1::map_to_curve(black_box(u)).unwrap()).collect::<Vec<_>>())
+        });
+    }
+    group.finish();
+}
+
+fn bench_hash(c: &mut Criterion) {
+    let mut group = c.benchmark_group("hash");
+    group.bench_function("g1", |b| b.iter(|| AffineG1::hash_default(black_box(b"abc"))));
+    group.bench_function("g2", |b| b.iter(|| AffineG2::hash_default(black_box(b"abc"))));
+    group.finish();
+}
+
+fn bench_clear_cofactor(c: &mut Criterion) {
+    let u = Fq2::hash_to_field(b"abc", AffineG2::DST, 1).unwrap()[0];
+    let q = AffineG2::map_to_curve(u).unwrap();
+
+    c.bench_function("clear_cofactor/g2", |b| b.iter(|| AffineG2::clear_cofactor(black_box(q))));
+}
+
+criterion_group!(benches, bench_map_to_curve, bench_batch_map_to_curve, bench_hash, bench_clear_cofactor);
+criterion_main!(benches);
@@ -0,0 +1,41 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use rand::thread_rng;
+use sp1_hash2curve::{msm_g1, msm_g2};
+use substrate_bn::{AffineG1, AffineG2, Fr, Group, G1, G2};
+
+fn bench_msm_g1(c: &mut Criterion) {
+    let mut rng = thread_rng();
+
+    let mut group = c.benchmark_group("msm_g1");
+    group.sample_size(10);
+    for n in [64, 256, 1024, 4096] {
+        let points: Vec<AffineG1> = (0..n).map(|_| (G1::one() * Fr::random(&mut rng)).into()).collect();
+        let scalars: Vec<Fr> = (0..n).map(|_| Fr::random(&mut rng)).collect();
+
+        group.bench_with_input(BenchmarkId::new("pippenger", n), &n, |b, _| b.iter(|| msm_g1(&points, &scalars)));
+        group.bench_with_input(BenchmarkId::new("naive", n), &n, |b, _| {
+            b.iter(|| points.iter().zip(&scalars).fold(G1::zero(), |acc, (&p, &s)| acc + G1::from(p) * s))
+        });
+    }
+    group.finish();
+}
+
+fn bench_msm_g2(c: &mut Criterion) {
+    let mut rng = thread_rng();
+
+    let mut group = c.benchmark_group("msm_g2");
+    group.sample_size(10);
+    for n in [64, 256, 1024, 4096] {
+        let points: Vec<AffineG2> = (0..n).map(|_| (G2::one() * Fr::random(&mut rng)).into()).collect();
+        let scalars: Vec<Fr> = (0..n).map(|_| Fr::random(&mut rng)).collect();
+
+        group.bench_with_input(BenchmarkId::new("pippenger", n), &n, |b, _| b.iter(|| msm_g2(&points, &scalars)));
+        group.bench_with_input(BenchmarkId::new("naive", n), &n, |b, _| {
+            b.iter(|| points.iter().zip(&scalars).fold(G2::zero(), |acc, (&p, &s)| acc + G2::from(p) * s))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_msm_g1, bench_msm_g2);
+criterion_main!(benches);
@@ -0,0 +1,17 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use sp1_hash2curve::HashToCurve;
+use substrate_bn::{AffineG1, Fq};
+
+// Even and odd inputs should take the same time; compare the two reports
+fn bench_sgn0(c: &mut Criterion) {
+    let even = Fq::from_str("3106428082009635406807032300288584059640244342225966151234406580587112112014").unwrap();
+    let odd = Fq::from_str("7951370986911800256774597109927097176311261202951929331835478768207980370345").unwrap();
+
+    let mut group = c.benchmark_group("sgn0");
+    group.bench_function("even", |b| b.iter(|| AffineG1::sgn0(black_box(even))));
+    group.bench_function("odd", |b| b.iter(|| AffineG1::sgn0(black_box(odd))));
+    group.finish();
+}
+
+criterion_group!(benches, bench_sgn0);
+criterion_main!(benches);
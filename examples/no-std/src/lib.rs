@@ -0,0 +1,32 @@
+// Compile check for the no_std + alloc build. It only has to build, e.g. with
+//
+//     cargo build --target riscv32im-unknown-none-elf
+//
+// from this directory (or against the SP1 toolchain's riscv32im-succinct-zkvm-elf). A std import
+// anywhere in the no_std surface fails it. Linking a guest binary additionally needs an allocator
+// and a critical-section implementation, which the zkVM runtime provides
+#![no_std]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+
+use sp1_hash2curve::{expand_message_xmd, msm_g1, G1Encoding, HashToCurve};
+use substrate_bn::{AffineG1, AffineG2, Fr};
+
+pub fn hash_g1(msg: &[u8]) -> [u8; 64] {
+    AffineG1::hash(msg, AffineG1::DST).to_uncompressed()
+}
+
+pub fn hash_g2(msg: &[u8]) -> AffineG2 {
+    AffineG2::hash(msg, AffineG2::DST)
+}
+
+pub fn uniform_bytes(msg: &[u8], len: usize) -> Vec<u8> {
+    expand_message_xmd(msg, AffineG1::DST, len).unwrap()
+}
+
+pub fn weighted_sum(msgs: &[&[u8]], scalars: &[Fr]) -> AffineG1 {
+    let points = AffineG1::hash_batch(msgs, AffineG1::DST);
+    msm_g1(&points, scalars)
+}
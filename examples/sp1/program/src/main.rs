@@ -0,0 +1,12 @@
+// Guest: reads a message, hashes it to G1 and commits the 64-byte uncompressed point
+#![no_main]
+sp1_zkvm::entrypoint!(main);
+
+use sp1_hash2curve::{G1Encoding, HashToCurve};
+use substrate_bn::AffineG1;
+
+pub fn main() {
+    let msg = sp1_zkvm::io::read_vec();
+    let p = AffineG1::hash(&msg, AffineG1::DST);
+    sp1_zkvm::io::commit_slice(&p.to_uncompressed());
+}
@@ -0,0 +1,22 @@
+// Executes the guest without proving and prints its cycle count. Run it once as is and once with
+// the "sp1" feature removed from the program's dependency to see what the SHA-256 precompile saves:
+//
+//     cd examples/sp1/script && cargo run --release -- abc
+use sp1_hash2curve::{G1Encoding, HashToCurve};
+use sp1_sdk::{include_elf, ProverClient, SP1Stdin};
+use substrate_bn::AffineG1;
+
+const ELF: &[u8] = include_elf!("hash-g1-program");
+
+fn main() {
+    let msg = std::env::args().nth(1).unwrap_or_else(|| "abc".into()).into_bytes();
+
+    let mut stdin = SP1Stdin::new();
+    stdin.write_vec(msg.clone());
+    let (output, report) = ProverClient::new().execute(ELF, stdin).run().unwrap();
+
+    // The guest must agree with the host
+    assert_eq!(output.as_slice(), AffineG1::hash(&msg, AffineG1::DST).to_uncompressed());
+    println!("point: {}", hex::encode(output.as_slice()));
+    println!("cycles: {}", report.total_instruction_count());
+}
@@ -0,0 +1,7 @@
+#!/usr/bin/env sh
+# Builds the JavaScript package into pkg/ and runs the Node checks against it
+set -e
+cd "$(dirname "$0")/.."
+
+wasm-pack build --target nodejs --out-dir pkg -- --features wasm
+node --test tests/js/
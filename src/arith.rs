@@ -0,0 +1,164 @@
+use substrate_bn::{Fr, Group, G1, G2};
+
+// Group operations behind hashing, cofactor clearing and commitments. substrate_bn by default; with
+// `sp1` inside the zkVM, G1 addition and doubling go through SP1's bn254 precompiles. SP1 has no G2
+// precompile, so G2 stays in software on every backend
+
+pub(crate) fn g1_add(a: G1, b: G1) -> G1 {
+    backend::g1_add(a, b)
+}
+
+pub(crate) fn g1_mul(p: G1, k: Fr) -> G1 {
+    backend::g1_mul(p, k)
+}
+
+pub(crate) fn g2_add(a: G2, b: G2) -> G2 {
+    a + b
+}
+
+pub(crate) fn g2_mul(p: G2, k: Fr) -> G2 {
+    p * k
+}
+
+// Lets the generic MSM pick the backend addition for its group
+pub(crate) trait Arith: Group {
+    fn add_point(self, other: Self) -> Self;
+}
+
+impl Arith for G1 {
+    fn add_point(self, other: Self) -> Self {
+        g1_add(self, other)
+    }
+}
+
+impl Arith for G2 {
+    fn add_point(self, other: Self) -> Self {
+        g2_add(self, other)
+    }
+}
+
+// Left-to-right double-and-add over the big-endian bits of k. The zkVM backend needs it because the
+// precompiles only add and double; it is generic so the host tests can run it on substrate_bn
+#[cfg_attr(not(all(feature = "sp1", target_os = "zkvm")), allow(dead_code))]
+fn double_and_add<P: Copy>(p: P, k: Fr, zero: P, add: impl Fn(P, P) -> P, double: impl Fn(P) -> P) -> P {
+    let mut bytes = [0u8; 32];
+    k.to_big_endian(&mut bytes).unwrap();
+    let mut acc = zero;
+    for byte in bytes {
+        for i in (0..8).rev() {
+            acc = double(acc);
+            if (byte >> i) & 1 == 1 {
+                acc = add(acc, p);
+            }
+        }
+    }
+    acc
+}
+
+#[cfg(not(all(feature = "sp1", target_os = "zkvm")))]
+mod backend {
+    use substrate_bn::{Fr, G1};
+
+    pub(super) fn g1_add(a: G1, b: G1) -> G1 {
+        a + b
+    }
+
+    pub(super) fn g1_mul(p: G1, k: Fr) -> G1 {
+        p * k
+    }
+}
+
+#[cfg(all(feature = "sp1", target_os = "zkvm"))]
+mod backend {
+    use sp1_lib::{bn254::Bn254Point, utils::AffinePoint};
+    use substrate_bn::{AffineG1, Fq, Fr, Group, G1};
+
+    use super::double_and_add;
+
+    // The precompile takes affine x || y as little-endian words. `None` is the identity, which it
+    // has no encoding for
+    fn to_sp1(p: G1) -> Option<Bn254Point> {
+        let p = AffineG1::from_jacobian(p)?;
+        let mut bytes = [0u8; 64];
+        p.x().to_big_endian(&mut bytes[..32]).unwrap();
+        p.y().to_big_endian(&mut bytes[32..]).unwrap();
+        bytes[..32].reverse();
+        bytes[32..].reverse();
+        Some(Bn254Point::from_le_bytes(&bytes))
+    }
+
+    fn from_sp1(p: Option<Bn254Point>) -> G1 {
+        let Some(p) = p else { return G1::zero() };
+        let mut bytes = p.to_le_bytes();
+        bytes[..32].reverse();
+        bytes[32..].reverse();
+        AffineG1::new(Fq::from_slice(&bytes[..32]).unwrap(), Fq::from_slice(&bytes[32..]).unwrap()).unwrap().into()
+    }
+
+    // The add precompile requires distinct x, so equal points double and opposite ones cancel
+    fn add(a: Option<Bn254Point>, b: Option<Bn254Point>) -> Option<Bn254Point> {
+        let (mut a, b) = match (a, b) {
+            (None, q) | (q, None) => return q,
+            (Some(a), Some(b)) => (a, b),
+        };
+        let (ab, bb) = (a.to_le_bytes(), b.to_le_bytes());
+        if ab[..32] == bb[..32] {
+            if ab[32..] != bb[32..] {
+                return None;
+            }
+            a.double();
+        } else {
+            a.add_assign(&b);
+        }
+        Some(a)
+    }
+
+    fn double(a: Option<Bn254Point>) -> Option<Bn254Point> {
+        add(a.clone(), a)
+    }
+
+    pub(super) fn g1_add(a: G1, b: G1) -> G1 {
+        from_sp1(add(to_sp1(a), to_sp1(b)))
+    }
+
+    pub(super) fn g1_mul(p: G1, k: Fr) -> G1 {
+        from_sp1(double_and_add(to_sp1(p), k, None, add, double))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::thread_rng;
+
+    // The zkVM backend only exists on the zkVM, so the host checks its scalar multiplication ladder
+    // over substrate_bn's own add and double, and the default backend against the operators
+    #[test]
+    fn test_double_and_add() {
+        let mut rng = thread_rng();
+        let p = G1::one() * Fr::random(&mut rng);
+        for k in [Fr::zero(), Fr::one(), -Fr::one(), Fr::random(&mut rng)] {
+            assert!(double_and_add(p, k, G1::zero(), |a, b| a + b, |a| a + a) == p * k);
+        }
+        let q = G2::one() * Fr::random(&mut rng);
+        let k = Fr::random(&mut rng);
+        assert!(double_and_add(q, k, G2::zero(), |a, b| a + b, |a| a + a) == q * k);
+    }
+
+    #[test]
+    fn test_backend_matches() {
+        let mut rng = thread_rng();
+        let (p, q) = (G1::one() * Fr::random(&mut rng), G1::one() * Fr::random(&mut rng));
+        let k = Fr::random(&mut rng);
+        assert!(g1_add(p, q) == p + q);
+        assert!(g1_add(p, p) == p + p);
+        assert!(g1_add(p, -p).is_zero());
+        assert!(g1_add(p, G1::zero()) == p);
+        assert!(g1_mul(p, k) == p * k);
+        assert!(g1_mul(p, Fr::zero()).is_zero());
+
+        let (p, q) = (G2::one() * Fr::random(&mut rng), G2::one() * Fr::random(&mut rng));
+        assert!(g2_add(p, q) == p + q);
+        assert!(g2_mul(p, k) == p * k);
+    }
+}
@@ -0,0 +1,166 @@
+use ark_ff::{BigInteger, PrimeField};
+use substrate_bn::{AffineG1, AffineG2, Fq, Fq2, Fr};
+
+use crate::DeserializationError;
+
+/// Converts to and from `ark_bn254` types. Both sides are foreign, so the `substrate_bn` value is
+/// wrapped: `G1Affine::from(Ark(p))` one way and `Ark::<AffineG1>::try_from(q)?.0` the other.
+/// Elements cross as canonical big-endian integers, so neither side's Montgomery form leaks through
+pub struct Ark<T>(pub T);
+
+fn from_ark<F: PrimeField>(x: F) -> [u8; 32] {
+    let mut bytes = [0u8; 32];
+    let be = x.into_bigint().to_bytes_be();
+    bytes[32 - be.len()..].copy_from_slice(&be);
+    bytes
+}
+
+impl From<Ark<Fq>> for ark_bn254::Fq {
+    fn from(x: Ark<Fq>) -> Self {
+        let mut bytes = [0u8; 32];
+        x.0.to_big_endian(&mut bytes).unwrap();
+        ark_bn254::Fq::from_be_bytes_mod_order(&bytes)
+    }
+}
+
+// Canonical arkworks elements are below p, so the reverse direction can't fail
+impl From<ark_bn254::Fq> for Ark<Fq> {
+    fn from(x: ark_bn254::Fq) -> Self {
+        Ark(Fq::from_slice(&from_ark(x)).unwrap())
+    }
+}
+
+impl From<Ark<Fr>> for ark_bn254::Fr {
+    fn from(x: Ark<Fr>) -> Self {
+        let mut bytes = [0u8; 32];
+        x.0.to_big_endian(&mut bytes).unwrap();
+        ark_bn254::Fr::from_be_bytes_mod_order(&bytes)
+    }
+}
+
+impl From<ark_bn254::Fr> for Ark<Fr> {
+    fn from(x: ark_bn254::Fr) -> Self {
+        Ark(Fr::from_slice(&from_ark(x)).unwrap())
+    }
+}
+
+// Both libraries build Fq2 as Fq[u] / (u² + 1), so the coefficients line up directly
+impl From<Ark<Fq2>> for ark_bn254::Fq2 {
+    fn from(x: Ark<Fq2>) -> Self {
+        ark_bn254::Fq2::new(Ark(x.0.real()).into(), Ark(x.0.imaginary()).into())
+    }
+}
+
+impl From<ark_bn254::Fq2> for Ark<Fq2> {
+    fn from(x: ark_bn254::Fq2) -> Self {
+        Ark(Fq2::new(Ark::from(x.c0).0, Ark::from(x.c1).0))
+    }
+}
+
+// An `AffineG1` is always a valid point, so the unchecked constructor is sound here
+impl From<Ark<AffineG1>> for ark_bn254::G1Affine {
+    fn from(p: Ark<AffineG1>) -> Self {
+        ark_bn254::G1Affine::new_unchecked(Ark(p.0.x()).into(), Ark(p.0.y()).into())
+    }
+}
+
+impl TryFrom<ark_bn254::G1Affine> for Ark<AffineG1> {
+    type Error = DeserializationError;
+
+    fn try_from(p: ark_bn254::G1Affine) -> Result<Self, Self::Error> {
+        if p.infinity {
+            return Err(DeserializationError::UnexpectedInfinity);
+        }
+        Ok(Ark(AffineG1::new(Ark::from(p.x).0, Ark::from(p.y).0)?))
+    }
+}
+
+impl From<Ark<AffineG2>> for ark_bn254::G2Affine {
+    fn from(p: Ark<AffineG2>) -> Self {
+        ark_bn254::G2Affine::new_unchecked(Ark(p.0.x()).into(), Ark(p.0.y()).into())
+    }
+}
+
+impl TryFrom<ark_bn254::G2Affine> for Ark<AffineG2> {
+    type Error = DeserializationError;
+
+    fn try_from(p: ark_bn254::G2Affine) -> Result<Self, Self::Error> {
+        if p.infinity {
+            return Err(DeserializationError::UnexpectedInfinity);
+        }
+        Ok(Ark(AffineG2::new(Ark::from(p.x).0, Ark::from(p.y).0)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_ec::{AffineRepr, CurveGroup};
+    use rand::thread_rng;
+    use substrate_bn::{Group, G1, G2};
+
+    use crate::HashToCurve;
+
+    const MSGS: [&[u8]; 4] = [b"", b"abc", b"abcdef0123456789", &[b'q'; 128]];
+
+    #[test]
+    fn test_field_round_trip() {
+        let mut rng = thread_rng();
+        for _ in 0..8 {
+            let x = Fq::random(&mut rng);
+            assert!(Ark::<Fq>::from(ark_bn254::Fq::from(Ark(x))).0 == x);
+            let x = Fr::random(&mut rng);
+            assert!(Ark::<Fr>::from(ark_bn254::Fr::from(Ark(x))).0 == x);
+            let x = Fq2::new(Fq::random(&mut rng), Fq::random(&mut rng));
+            assert!(Ark::<Fq2>::from(ark_bn254::Fq2::from(Ark(x))).0 == x);
+        }
+
+        // Multiplication agrees, which a Montgomery-form mix-up would break
+        let (a, b) = (Fq::random(&mut rng), Fq::random(&mut rng));
+        assert!(ark_bn254::Fq::from(Ark(a * b)) == ark_bn254::Fq::from(Ark(a)) * ark_bn254::Fq::from(Ark(b)));
+        assert!(ark_bn254::Fq::from(Ark(Fq::one())) == ark_bn254::Fq::from(1u64));
+    }
+
+    #[test]
+    fn test_hashed_points_valid_in_ark() {
+        for msg in MSGS {
+            let p = ark_bn254::G1Affine::from(Ark(AffineG1::hash(msg, AffineG1::DST)));
+            assert!(p.is_on_curve() && p.is_in_correct_subgroup_assuming_on_curve());
+            assert!(Ark::<AffineG1>::try_from(p).unwrap().0 == AffineG1::hash(msg, AffineG1::DST));
+
+            let p = ark_bn254::G2Affine::from(Ark(AffineG2::hash(msg, AffineG2::DST)));
+            assert!(p.is_on_curve() && p.is_in_correct_subgroup_assuming_on_curve());
+            assert!(Ark::<AffineG2>::try_from(p).unwrap().0 == AffineG2::hash(msg, AffineG2::DST));
+        }
+    }
+
+    #[test]
+    fn test_generators_agree() {
+        assert!(ark_bn254::G1Affine::from(Ark(AffineG1::from(G1::one()))) == ark_bn254::G1Affine::generator());
+        assert!(ark_bn254::G2Affine::from(Ark(AffineG2::from(G2::one()))) == ark_bn254::G2Affine::generator());
+    }
+
+    #[test]
+    fn test_scalar_mul_agrees() {
+        let mut rng = thread_rng();
+        for _ in 0..4 {
+            let (a, b) = (Fr::random(&mut rng), Fr::random(&mut rng));
+            let (a_ark, b_ark) = (ark_bn254::Fr::from(Ark(a)), ark_bn254::Fr::from(Ark(b)));
+
+            let p = (ark_bn254::G1Affine::generator() * a_ark).into_affine();
+            let q = Ark::<AffineG1>::try_from(p).unwrap().0;
+            assert!(ark_bn254::G1Affine::from(Ark(AffineG1::from(G1::from(q) * b))) == (p * b_ark).into_affine());
+
+            let p = (ark_bn254::G2Affine::generator() * a_ark).into_affine();
+            let q = Ark::<AffineG2>::try_from(p).unwrap().0;
+            assert!(ark_bn254::G2Affine::from(Ark(AffineG2::from(G2::from(q) * b))) == (p * b_ark).into_affine());
+        }
+    }
+
+    #[test]
+    fn test_rejects_invalid() {
+        assert_eq!(Ark::<AffineG1>::try_from(ark_bn254::G1Affine::identity()).err(), Some(DeserializationError::UnexpectedInfinity));
+        let off_curve = ark_bn254::G1Affine::new_unchecked(1u64.into(), 3u64.into());
+        assert_eq!(Ark::<AffineG1>::try_from(off_curve).err(), Some(DeserializationError::NotOnCurve));
+    }
+}
@@ -0,0 +1,259 @@
+use alloc::{collections::BTreeSet, vec, vec::Vec};
+
+use rand_core::RngCore;
+use substrate_bn::{pairing_batch, AffineG1, AffineG2, Fr, Group, Gt, G1, G2};
+
+use crate::fr::hash_to_fr_indexed;
+use crate::{hash_to_fr, G1Encoding, G2Encoding, HashToCurve};
+
+// Minimal-pubkey-size variant: public keys in G1, signatures in G2
+const DST: &[u8] = b"BLS_SIG_BN254G2_XMD:SHA-256_SVDW_RO_NUL_";
+
+const KEYGEN_DST: &[u8] = b"BLS-SIG-KEYGEN-SALT-";
+
+const BATCH_DST: &[u8] = b"BLS-SIG-BATCH-VERIFY-BN254_XMD:SHA-256_";
+
+pub struct BlsPrivateKey(pub Fr);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BlsPublicKey(pub AffineG1);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BlsSignature(pub AffineG2);
+
+impl BlsPrivateKey {
+    /// Deterministic key from seed material, which should carry at least 32 bytes of entropy
+    pub fn from_seed(seed: &[u8]) -> BlsPrivateKey {
+        BlsPrivateKey(hash_to_fr(seed, KEYGEN_DST))
+    }
+
+    pub fn random<R: RngCore>(rng: &mut R) -> BlsPrivateKey {
+        BlsPrivateKey(Fr::random(rng))
+    }
+}
+
+impl BlsPublicKey {
+    pub fn from_private(sk: &BlsPrivateKey) -> BlsPublicKey {
+        BlsPublicKey((G1::one() * sk.0).into())
+    }
+
+    /// Only safe for `verify_aggregate` when every key has a proof of possession; otherwise use
+    /// `verify_multi_message`
+    pub fn aggregate(pks: &[BlsPublicKey]) -> BlsPublicKey {
+        assert!(!pks.is_empty(), "cannot aggregate zero public keys");
+        BlsPublicKey(pks.iter().fold(G1::zero(), |acc, pk| acc + pk.0.into()).into())
+    }
+}
+
+impl BlsSignature {
+    pub fn aggregate(sigs: &[BlsSignature]) -> BlsSignature {
+        assert!(!sigs.is_empty(), "cannot aggregate zero signatures");
+        BlsSignature(sigs.iter().fold(G2::zero(), |acc, sig| acc + sig.0.into()).into())
+    }
+}
+
+pub fn sign(msg: &[u8], sk: &BlsPrivateKey) -> BlsSignature {
+    BlsSignature((G2::from(AffineG2::hash(msg, DST)) * sk.0).into())
+}
+
+// e(G1, sig) == e(pk, H(msg)), checked as e(-G1, sig) · e(pk, H(msg)) == 1 to share the final exponentiation
+pub fn verify(msg: &[u8], pk: &BlsPublicKey, sig: &BlsSignature) -> bool {
+    let h = AffineG2::hash(msg, DST);
+    pairing_batch(&[(-G1::one(), sig.0.into()), (pk.0.into(), h.into())]) == Gt::one()
+}
+
+// Every signer signed the same message, so the aggregate verifies like a single signature
+pub fn verify_aggregate(msg: &[u8], agg_pk: &BlsPublicKey, agg_sig: &BlsSignature) -> bool {
+    verify(msg, agg_pk, agg_sig)
+}
+
+// e(G1, agg_sig) == Π e(pk_i, H(m_i)). Messages must be pairwise distinct, otherwise a rogue key
+// could cancel an honest signer's share
+pub fn verify_multi_message(msgs: &[&[u8]], pks: &[BlsPublicKey], agg_sig: &BlsSignature) -> bool {
+    if msgs.is_empty() || msgs.len() != pks.len() {
+        return false;
+    }
+    let distinct: BTreeSet<&[u8]> = msgs.iter().copied().collect();
+    if distinct.len() != msgs.len() {
+        return false;
+    }
+
+    let mut pairs = vec![(-G1::one(), G2::from(agg_sig.0))];
+    pairs.extend(msgs.iter().zip(pks).map(|(msg, pk)| (G1::from(pk.0), G2::from(AffineG2::hash(msg, DST)))));
+    pairing_batch(&pairs) == Gt::one()
+}
+
+// Random linear combination Π e(r_i·pk_i, H(m_i)) == e(G1, Σ r_i·sig_i), sharing one Miller loop
+// pass and final exponentiation. The r_i are hashed from every input, one per index, so a forger
+// cannot choose signatures that cancel under them
+pub fn verify_batch(msgs: &[&[u8]], pks: &[BlsPublicKey], sigs: &[BlsSignature]) -> bool {
+    if msgs.is_empty() || msgs.len() != pks.len() || msgs.len() != sigs.len() {
+        return false;
+    }
+
+    let mut seed = Vec::new();
+    for ((msg, pk), sig) in msgs.iter().zip(pks).zip(sigs) {
+        seed.extend((msg.len() as u64).to_be_bytes());
+        seed.extend(*msg);
+        seed.extend(pk.0.to_uncompressed());
+        seed.extend(sig.0.to_uncompressed());
+    }
+    let Ok(rs) = hash_to_fr_indexed(&seed, BATCH_DST, msgs.len()) else {
+        return false;
+    };
+
+    let sig_sum = sigs.iter().zip(&rs).fold(G2::zero(), |acc, (sig, &r)| acc + G2::from(sig.0) * r);
+    let mut pairs = vec![(-G1::one(), sig_sum)];
+    pairs.extend(
+        msgs.iter().zip(pks).zip(&rs).map(|((msg, pk), &r)| (G1::from(pk.0) * r, G2::from(AffineG2::hash(msg, DST)))),
+    );
+    pairing_batch(&pairs) == Gt::one()
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::Zeroize for BlsPrivateKey {
+    fn zeroize(&mut self) {
+        crate::wipe(&mut self.0, Fr::zero());
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for BlsPrivateKey {
+    fn drop(&mut self) {
+        zeroize::Zeroize::zeroize(self);
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::ZeroizeOnDrop for BlsPrivateKey {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::thread_rng;
+
+    fn keypair() -> (BlsPrivateKey, BlsPublicKey) {
+        let sk = BlsPrivateKey::random(&mut thread_rng());
+        let pk = BlsPublicKey::from_private(&sk);
+        (sk, pk)
+    }
+
+    #[test]
+    fn test_sign_verify() {
+        let (sk, pk) = keypair();
+        let sig = sign(b"abc", &sk);
+        assert!(verify(b"abc", &pk, &sig));
+    }
+
+    #[test]
+    fn test_verify_rejects() {
+        let (sk, pk) = keypair();
+        let (_, other_pk) = keypair();
+        let sig = sign(b"abc", &sk);
+
+        assert!(!verify(b"abd", &pk, &sig));
+        assert!(!verify(b"abc", &other_pk, &sig));
+        assert!(!verify(b"abc", &pk, &sign(b"abc", &BlsPrivateKey(sk.0 + Fr::one()))));
+    }
+
+    #[test]
+    fn test_from_seed() {
+        let seed = [7u8; 32];
+        let sk = BlsPrivateKey::from_seed(&seed);
+        assert!(sk.0 == BlsPrivateKey::from_seed(&seed).0);
+        assert!(sk.0 == hash_to_fr(&seed, KEYGEN_DST));
+        assert!(sk.0 != BlsPrivateKey::from_seed(&[8u8; 32]).0);
+
+        let pk = BlsPublicKey::from_private(&sk);
+        assert_eq!(pk, BlsPublicKey::from_private(&BlsPrivateKey::from_seed(&seed)));
+        assert!(verify(b"abc", &pk, &sign(b"abc", &sk)));
+    }
+
+    // The IETF BLS draft only publishes BLS12-381 vectors, so these check the BN254 scheme against itself
+    #[test]
+    fn test_verify_aggregate() {
+        let keys: Vec<_> = (0..4).map(|_| keypair()).collect();
+        let sigs: Vec<_> = keys.iter().map(|(sk, _)| sign(b"abc", sk)).collect();
+        let pks: Vec<_> = keys.iter().map(|(_, pk)| *pk).collect();
+
+        let agg_sig = BlsSignature::aggregate(&sigs);
+        let agg_pk = BlsPublicKey::aggregate(&pks);
+        assert!(verify_aggregate(b"abc", &agg_pk, &agg_sig));
+        assert!(!verify_aggregate(b"abd", &agg_pk, &agg_sig));
+        assert!(!verify_aggregate(b"abc", &BlsPublicKey::aggregate(&pks[..3]), &agg_sig));
+
+        // The aggregate key is the key of the summed secret
+        let sk_sum = keys.iter().fold(Fr::zero(), |acc, (sk, _)| acc + sk.0);
+        assert_eq!(agg_pk, BlsPublicKey::from_private(&BlsPrivateKey(sk_sum)));
+        assert_eq!(agg_sig, sign(b"abc", &BlsPrivateKey(sk_sum)));
+    }
+
+    #[test]
+    fn test_verify_multi_message() {
+        let msgs: [&[u8]; 3] = [b"a", b"b", b"c"];
+        let keys: Vec<_> = (0..3).map(|_| keypair()).collect();
+        let pks: Vec<_> = keys.iter().map(|(_, pk)| *pk).collect();
+        let agg_sig = BlsSignature::aggregate(&keys.iter().zip(msgs).map(|((sk, _), msg)| sign(msg, sk)).collect::<Vec<_>>());
+
+        assert!(verify_multi_message(&msgs, &pks, &agg_sig));
+        assert!(!verify_multi_message(&[b"a", b"c", b"b"], &pks, &agg_sig));
+        assert!(!verify_multi_message(&msgs[..2], &pks[..2], &agg_sig));
+        assert!(!verify_multi_message(&msgs, &pks[..2], &agg_sig));
+
+        // Repeated messages are refused outright
+        let sig = BlsSignature::aggregate(&[sign(b"a", &keys[0].0), sign(b"a", &keys[1].0)]);
+        assert!(!verify_multi_message(&[b"a", b"a"], &pks[..2], &sig));
+    }
+
+    #[test]
+    fn test_verify_batch() {
+        let msgs: Vec<Vec<u8>> = (0..100u32).map(|i| i.to_le_bytes().to_vec()).collect();
+        let msgs: Vec<&[u8]> = msgs.iter().map(|m| m.as_slice()).collect();
+        let keys: Vec<_> = (0..100).map(|_| keypair()).collect();
+        let pks: Vec<_> = keys.iter().map(|(_, pk)| *pk).collect();
+        let mut sigs: Vec<_> = keys.iter().zip(&msgs).map(|((sk, _), msg)| sign(msg, sk)).collect();
+        assert!(verify_batch(&msgs, &pks, &sigs));
+
+        // A single bad signature anywhere sinks the batch
+        for i in [0, 37, 99] {
+            let honest = sigs[i];
+            sigs[i] = sign(msgs[i], &BlsPrivateKey(keys[i].0 .0 + Fr::one()));
+            assert!(!verify_batch(&msgs, &pks, &sigs));
+            sigs[i] = honest;
+        }
+
+        // Swapping two signatures keeps the plain sum but not the weighted one
+        sigs.swap(3, 4);
+        assert!(!verify_batch(&msgs, &pks, &sigs));
+        assert!(!verify_batch(&msgs[..99], &pks, &sigs));
+    }
+
+    // Past the 170 weights a single XMD expansion can produce
+    #[test]
+    fn test_verify_batch_large() {
+        let msgs: Vec<Vec<u8>> = (0..171u32).map(|i| i.to_le_bytes().to_vec()).collect();
+        let msgs: Vec<&[u8]> = msgs.iter().map(|m| m.as_slice()).collect();
+        let keys: Vec<_> = (0..171).map(|_| keypair()).collect();
+        let pks: Vec<_> = keys.iter().map(|(_, pk)| *pk).collect();
+        let mut sigs: Vec<_> = keys.iter().zip(&msgs).map(|((sk, _), msg)| sign(msg, sk)).collect();
+        assert!(verify_batch(&msgs, &pks, &sigs));
+
+        sigs[170] = sigs[0];
+        assert!(!verify_batch(&msgs, &pks, &sigs));
+    }
+
+    #[cfg(feature = "zeroize")]
+    #[test]
+    fn test_zeroize() {
+        use zeroize::{Zeroize, ZeroizeOnDrop};
+
+        // Reading the key back after drop would be undefined behaviour, so this checks the wipe
+        // that `Drop` runs and that the type is marked as wiping on drop
+        fn assert_zeroize_on_drop<T: ZeroizeOnDrop>() {}
+        assert_zeroize_on_drop::<BlsPrivateKey>();
+
+        let mut sk = BlsPrivateKey(Fr::random(&mut thread_rng()));
+        sk.zeroize();
+        assert!(sk.0 == Fr::zero());
+    }
+}
@@ -0,0 +1,154 @@
+use core::fmt;
+
+use substrate_bn::{AffineG1, AffineG2, Fq, Fq2};
+
+use crate::DeserializationError;
+
+/// Formats field elements and points as 0x-prefixed big-endian hex: `0x…` for `Fq`, `(re, im)` for
+/// `Fq2`, and `(x, y)` of those for points. `FromHex` parses the same strings
+pub struct DisplayHex<T>(pub T);
+
+impl fmt::Display for DisplayHex<Fq> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut bytes = [0u8; 32];
+        self.0.to_big_endian(&mut bytes).unwrap();
+        write!(f, "0x")?;
+        bytes.iter().try_for_each(|b| write!(f, "{:02x}", b))
+    }
+}
+
+impl fmt::Display for DisplayHex<Fq2> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {})", DisplayHex(self.0.real()), DisplayHex(self.0.imaginary()))
+    }
+}
+
+impl fmt::Display for DisplayHex<AffineG1> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {})", DisplayHex(self.0.x()), DisplayHex(self.0.y()))
+    }
+}
+
+impl fmt::Display for DisplayHex<AffineG2> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {})", DisplayHex(self.0.x()), DisplayHex(self.0.y()))
+    }
+}
+
+/// Parses what `DisplayHex` prints. The 0x prefix is optional and short values are left-padded,
+/// but odd-length hex, more than 32 bytes and values >= p are rejected
+pub trait FromHex: Sized {
+    fn from_hex(s: &str) -> Result<Self, DeserializationError>;
+}
+
+// Splits "(a, b)" at its top-level comma
+fn split_pair(s: &str) -> Result<(&str, &str), DeserializationError> {
+    let inner = s.trim().strip_prefix('(').and_then(|s| s.strip_suffix(')')).ok_or(DeserializationError::InvalidEncoding)?;
+    let mut depth = 0;
+    for (i, c) in inner.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            ',' if depth == 0 => return Ok((&inner[..i], &inner[i + 1..])),
+            _ => {}
+        }
+    }
+    Err(DeserializationError::InvalidEncoding)
+}
+
+impl FromHex for Fq {
+    fn from_hex(s: &str) -> Result<Self, DeserializationError> {
+        let s = s.trim();
+        let digits = s.strip_prefix("0x").unwrap_or(s);
+        if digits.len() % 2 != 0 || digits.len() > 64 {
+            return Err(DeserializationError::InvalidEncoding);
+        }
+        let mut bytes = [0u8; 32];
+        hex::decode_to_slice(digits, &mut bytes[32 - digits.len() / 2..]).map_err(|_| DeserializationError::InvalidEncoding)?;
+        Fq::from_slice(&bytes).map_err(|_| DeserializationError::InvalidEncoding)
+    }
+}
+
+impl FromHex for Fq2 {
+    fn from_hex(s: &str) -> Result<Self, DeserializationError> {
+        let (re, im) = split_pair(s)?;
+        Ok(Fq2::new(Fq::from_hex(re)?, Fq::from_hex(im)?))
+    }
+}
+
+impl FromHex for AffineG1 {
+    fn from_hex(s: &str) -> Result<Self, DeserializationError> {
+        let (x, y) = split_pair(s)?;
+        Ok(AffineG1::new(Fq::from_hex(x)?, Fq::from_hex(y)?)?)
+    }
+}
+
+impl FromHex for AffineG2 {
+    fn from_hex(s: &str) -> Result<Self, DeserializationError> {
+        let (x, y) = split_pair(s)?;
+        Ok(AffineG2::new(Fq2::from_hex(x)?, Fq2::from_hex(y)?)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::thread_rng;
+    use substrate_bn::{Fr, Group, G1, G2};
+
+    use crate::HashToCurve;
+
+    #[test]
+    fn test_display() {
+        assert_eq!(DisplayHex(Fq::one()).to_string(), format!("0x{:064x}", 1));
+        let one = format!("0x{:064x}", 1);
+        let two = format!("0x{:064x}", 2);
+        assert_eq!(DisplayHex(AffineG1::one()).to_string(), format!("({}, {})", one, two));
+        assert_eq!(DisplayHex(Fq2::one()).to_string(), format!("({}, 0x{:064x})", one, 0));
+    }
+
+    #[test]
+    fn test_hex_vector() {
+        // The RFC 9380 BN254G1 vector for "abc", written as hex instead of decimal
+        let q = AffineG1::hash(b"abc", AffineG1::DST);
+        let expected = "(0x23f717bee89b1003957139f193e6be7da1df5f1374b26a4643b0378b5baf53d1, 0x04142f826b71ee574452dbc47e05bc3e1a647478403a7ba38b7b93948f4e151d)";
+        assert!(q == AffineG1::from_hex(expected).unwrap());
+        assert_eq!(DisplayHex(q).to_string(), expected);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let mut rng = thread_rng();
+        for _ in 0..8 {
+            let x = Fq::random(&mut rng);
+            assert!(Fq::from_hex(&DisplayHex(x).to_string()).unwrap() == x);
+            let x = Fq2::new(Fq::random(&mut rng), Fq::random(&mut rng));
+            assert!(Fq2::from_hex(&DisplayHex(x).to_string()).unwrap() == x);
+            let p: AffineG1 = (G1::one() * Fr::random(&mut rng)).into();
+            assert!(AffineG1::from_hex(&DisplayHex(p).to_string()).unwrap() == p);
+            let p: AffineG2 = (G2::one() * Fr::random(&mut rng)).into();
+            assert!(AffineG2::from_hex(&DisplayHex(p).to_string()).unwrap() == p);
+        }
+    }
+
+    #[test]
+    fn test_parse_forms() {
+        assert!(Fq::from_hex("0x03").unwrap() == Fq::from_str("3").unwrap());
+        assert!(Fq::from_hex("03").unwrap() == Fq::from_str("3").unwrap());
+        assert!(AffineG1::from_hex(" ( 0x01 ,0x02 ) ").unwrap() == AffineG1::one());
+    }
+
+    #[test]
+    fn test_rejects_invalid() {
+        let invalid = DeserializationError::InvalidEncoding;
+        assert_eq!(Fq::from_hex("0x123").err(), Some(invalid));
+        assert_eq!(Fq::from_hex(&format!("0x{:066x}", 1)).err(), Some(invalid));
+        assert_eq!(Fq::from_hex("0xzz").err(), Some(invalid));
+        // p itself
+        assert_eq!(Fq::from_hex("0x30644e72e131a029b85045b68181585d97816a916871ca8d3c208c16d87cfd47").err(), Some(invalid));
+
+        assert_eq!(Fq2::from_hex("0x01, 0x02").err(), Some(invalid));
+        assert_eq!(Fq2::from_hex("(0x01)").err(), Some(invalid));
+        assert_eq!(AffineG1::from_hex("(0x01, 0x03)").err(), Some(DeserializationError::NotOnCurve));
+    }
+}
@@ -0,0 +1,69 @@
+use alloc::string::String;
+use core::fmt;
+
+use substrate_bn::GroupError;
+
+use crate::ExpandError;
+
+#[derive(Debug)]
+pub enum HashToCurveError {
+    /// The mapped coordinates do not form a valid curve point
+    Group(GroupError),
+    /// Uniform bytes could not be decoded into a field element
+    InvalidEncoding(String),
+    /// The domain separation tag is empty
+    InvalidDst,
+    /// The requested number of field elements exceeds the expander's output limit
+    LenTooLarge,
+}
+
+impl fmt::Display for HashToCurveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HashToCurveError::Group(e) => write!(f, "invalid curve point: {:?}", e),
+            HashToCurveError::InvalidEncoding(msg) => write!(f, "invalid field element encoding: {}", msg),
+            HashToCurveError::InvalidDst => write!(f, "the domain separation tag is empty"),
+            HashToCurveError::LenTooLarge => write!(f, "requested output exceeds the expander's limit"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for HashToCurveError {}
+
+impl From<ExpandError> for HashToCurveError {
+    fn from(e: ExpandError) -> Self {
+        match e {
+            ExpandError::InvalidDst => HashToCurveError::InvalidDst,
+            ExpandError::OutputTooLarge => HashToCurveError::LenTooLarge,
+        }
+    }
+}
+
+impl From<GroupError> for HashToCurveError {
+    fn from(e: GroupError) -> Self {
+        HashToCurveError::Group(e)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        assert_eq!(HashToCurveError::InvalidDst.to_string(), "the domain separation tag is empty");
+        assert_eq!(
+            HashToCurveError::InvalidEncoding("bad".into()).to_string(),
+            "invalid field element encoding: bad"
+        );
+        assert_eq!(HashToCurveError::from(ExpandError::OutputTooLarge).to_string(), "requested output exceeds the expander's limit");
+
+        let e: Box<dyn std::error::Error> = Box::new(HashToCurveError::from(GroupError::NotOnCurve));
+        assert_eq!(e.to_string(), "invalid curve point: NotOnCurve");
+
+        let e: Box<dyn std::error::Error> = Box::new(ExpandError::OutputTooLarge);
+        assert_eq!(e.to_string(), "requested output needs more than 255 hash blocks");
+        assert_eq!(ExpandError::InvalidDst.to_string(), "the domain separation tag is empty");
+    }
+}
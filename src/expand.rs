@@ -0,0 +1,347 @@
+use alloc::{vec, vec::Vec};
+use core::fmt;
+
+use digest::{core_api::BlockSizeUser, ExtendableOutput, Update, XofReader};
+use sha2::{Sha384, Sha512, digest::Digest};
+use sha3::{Shake128, Shake256};
+
+// The one place SHA-256 is chosen. Under `sp1` it is SP1's patched sha2, whose compression function
+// becomes a precompile syscall inside the zkVM and is the plain software one everywhere else
+#[cfg(feature = "sp1")]
+pub(crate) type Sha256 = sha2_sp1::Sha256;
+#[cfg(not(feature = "sp1"))]
+pub(crate) type Sha256 = sha2::Sha256;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExpandError {
+    /// The domain separation tag is empty
+    InvalidDst,
+    /// The requested output needs more than 255 hash blocks
+    OutputTooLarge,
+}
+
+impl fmt::Display for ExpandError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExpandError::InvalidDst => write!(f, "the domain separation tag is empty"),
+            ExpandError::OutputTooLarge => write!(f, "requested output needs more than 255 hash blocks"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ExpandError {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum XofVariant {
+    Shake128,
+    Shake256,
+}
+
+const OVERSIZE_DST_PREFIX: &[u8] = b"H2C-OVERSIZE-DST-";
+
+// https://www.rfc-editor.org/rfc/rfc9380.html#name-expand_message_xmd
+//
+// DSTs over 255 bytes show up when a protocol builds its tag from variable parts (application name,
+// version, ciphersuite, context string). They are replaced by H("H2C-OVERSIZE-DST-" || DST) rather than
+// rejected, so such tags interoperate with other RFC 9380 implementations
+pub fn expand_message_xmd_with<H: Digest + BlockSizeUser>(msg: &[u8], dst: &[u8], len_in_bytes: usize) -> Result<Vec<u8>, ExpandError> {
+    let b_in_bytes = <H as Digest>::output_size();
+    let s_in_bytes = H::block_size();
+
+    let ell = (len_in_bytes + b_in_bytes - 1) / b_in_bytes;
+
+    if ell > 255 || len_in_bytes > u16::MAX as usize {
+        return Err(ExpandError::OutputTooLarge);
+    }
+    if dst.is_empty() {
+        return Err(ExpandError::InvalidDst);
+    }
+
+    // https://www.rfc-editor.org/rfc/rfc9380.html#name-using-dsts-longer-than-255-
+    let oversize_dst;
+    let dst = if dst.len() > 255 {
+        oversize_dst = H::new().chain_update(OVERSIZE_DST_PREFIX).chain_update(dst).finalize();
+        &oversize_dst[..]
+    } else {
+        dst
+    };
+
+    // b_0 = H(Z_pad || msg || I2OSP(len_in_bytes, 2) || I2OSP(0, 1) || DST_prime)
+    let b_0 = H::new()
+        .chain_update(vec![0u8; s_in_bytes])
+        .chain_update(msg)
+        .chain_update((len_in_bytes as u16).to_be_bytes())
+        .chain_update([0u8])
+        .chain_update(dst)
+        .chain_update([dst.len() as u8])
+        .finalize();
+
+    // b_1 = H(b_0 || I2OSP(1, 1) || DST_prime)
+    let mut b_i = H::new()
+        .chain_update(&b_0)
+        .chain_update([1u8])
+        .chain_update(dst)
+        .chain_update([dst.len() as u8])
+        .finalize();
+
+    let mut uniform_bytes = Vec::with_capacity(ell * b_in_bytes);
+    uniform_bytes.extend_from_slice(&b_i);
+
+    for i in 2..=ell {
+        // b_i = H(strxor(b_0, b_(i - 1)) || I2OSP(i, 1) || DST_prime)
+        let mut tmp = b_0.clone();
+        tmp.iter_mut()
+            .zip(&b_i)
+            .for_each(|(t, bi)| *t ^= bi);
+
+        b_i = H::new()
+            .chain_update(tmp)
+            .chain_update([i as u8])
+            .chain_update(dst)
+            .chain_update([dst.len() as u8])
+            .finalize();
+        uniform_bytes.extend_from_slice(&b_i);
+    }
+
+    uniform_bytes.truncate(len_in_bytes);
+    Ok(uniform_bytes)
+}
+
+pub fn expand_message_xmd(msg: &[u8], dst: &[u8], len_in_bytes: usize) -> Result<Vec<u8>, ExpandError> {
+    expand_message_xmd_with::<Sha256>(msg, dst, len_in_bytes)
+}
+
+pub fn expand_message_xmd_sha256(msg: &[u8], dst: &[u8], len_in_bytes: usize) -> Result<Vec<u8>, ExpandError> {
+    expand_message_xmd_with::<Sha256>(msg, dst, len_in_bytes)
+}
+
+pub fn expand_message_xmd_sha384(msg: &[u8], dst: &[u8], len_in_bytes: usize) -> Result<Vec<u8>, ExpandError> {
+    expand_message_xmd_with::<Sha384>(msg, dst, len_in_bytes)
+}
+
+pub fn expand_message_xmd_sha512(msg: &[u8], dst: &[u8], len_in_bytes: usize) -> Result<Vec<u8>, ExpandError> {
+    expand_message_xmd_with::<Sha512>(msg, dst, len_in_bytes)
+}
+
+// https://www.rfc-editor.org/rfc/rfc9380.html#name-expand_message_xof
+// `k` is the target security level in bits; it only sizes the reduced DST when `dst` exceeds 255 bytes
+pub fn expand_message_xof_with<H: Default + Update + ExtendableOutput>(msg: &[u8], dst: &[u8], len_in_bytes: usize, k: usize) -> Result<Vec<u8>, ExpandError> {
+    if len_in_bytes > u16::MAX as usize {
+        return Err(ExpandError::OutputTooLarge);
+    }
+    if dst.is_empty() {
+        return Err(ExpandError::InvalidDst);
+    }
+
+    let mut oversize_dst = vec![];
+    let dst = if dst.len() > 255 {
+        let mut hasher = H::default();
+        hasher.update(OVERSIZE_DST_PREFIX);
+        hasher.update(dst);
+        oversize_dst.resize((2 * k + 7) / 8, 0);
+        hasher.finalize_xof().read(&mut oversize_dst);
+        &oversize_dst[..]
+    } else {
+        dst
+    };
+
+    // msg_prime = msg || I2OSP(len_in_bytes, 2) || DST_prime
+    let mut hasher = H::default();
+    hasher.update(msg);
+    hasher.update(&(len_in_bytes as u16).to_be_bytes());
+    hasher.update(dst);
+    hasher.update(&[dst.len() as u8]);
+
+    let mut uniform_bytes = vec![0u8; len_in_bytes];
+    hasher.finalize_xof().read(&mut uniform_bytes);
+    Ok(uniform_bytes)
+}
+
+pub fn expand_message_xof_shake128(msg: &[u8], dst: &[u8], len_in_bytes: usize) -> Result<Vec<u8>, ExpandError> {
+    expand_message_xof_with::<Shake128>(msg, dst, len_in_bytes, 128)
+}
+
+pub fn expand_message_xof_shake256(msg: &[u8], dst: &[u8], len_in_bytes: usize) -> Result<Vec<u8>, ExpandError> {
+    expand_message_xof_with::<Shake256>(msg, dst, len_in_bytes, 256)
+}
+
+pub fn expand_message_xof(msg: &[u8], dst: &[u8], len_in_bytes: usize, variant: XofVariant) -> Result<Vec<u8>, ExpandError> {
+    match variant {
+        XofVariant::Shake128 => expand_message_xof_shake128(msg, dst, len_in_bytes),
+        XofVariant::Shake256 => expand_message_xof_shake256(msg, dst, len_in_bytes),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expand_message_xmd() {
+        // Test vectors taken from https://www.rfc-editor.org/rfc/rfc9380.html#appendix-K.1
+        let dst = b"QUUX-V01-CS02-with-expander-SHA256-128";
+
+        let vectors: [(&[u8], usize, &str); 10] = [
+            (b"", 0x20, "68a985b87eb6b46952128911f2a4412bbc302a9d759667f87f7a21d803f07235"),
+            (b"abc", 0x20, "d8ccab23b5985ccea865c6c97b6e5b8350e794e603b4b97902f53a8a0d605615"),
+            (b"abcdef0123456789", 0x20, "eff31487c770a893cfb36f912fbfcbff40d5661771ca4b2cb4eafe524333f5c1"),
+            (b"q128_qqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqq", 0x20, "b23a1d2b4d97b2ef7785562a7e8bac7eed54ed6e97e29aa51bfe3f12ddad1ff9"),
+            (b"a512_aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa", 0x20, "4623227bcc01293b8c130bf771da8c298dede7383243dc0993d2d94823958c4c"),
+            (b"", 0x80, "af84c27ccfd45d41914fdff5df25293e221afc53d8ad2ac06d5e3e29485dadbee0d121587713a3e0dd4d5e69e93eb7cd4f5df4cd103e188cf60cb02edc3edf18eda8576c412b18ffb658e3dd6ec849469b979d444cf7b26911a08e63cf31f9dcc541708d3491184472c2c29bb749d4286b004ceb5ee6b9a7fa5b646c993f0ced"),
+            (b"abc", 0x80, "abba86a6129e366fc877aab32fc4ffc70120d8996c88aee2fe4b32d6c7b6437a647e6c3163d40b76a73cf6a5674ef1d890f95b664ee0afa5359a5c4e07985635bbecbac65d747d3d2da7ec2b8221b17b0ca9dc8a1ac1c07ea6a1e60583e2cb00058e77b7b72a298425cd1b941ad4ec65e8afc50303a22c0f99b0509b4c895f40"),
+            (b"abcdef0123456789", 0x80, "ef904a29bffc4cf9ee82832451c946ac3c8f8058ae97d8d629831a74c6572bd9ebd0df635cd1f208e2038e760c4994984ce73f0d55ea9f22af83ba4734569d4bc95e18350f740c07eef653cbb9f87910d833751825f0ebefa1abe5420bb52be14cf489b37fe1a72f7de2d10be453b2c9d9eb20c7e3f6edc5a60629178d9478df"),
+            (b"q128_qqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqq", 0x80, "80be107d0884f0d881bb460322f0443d38bd222db8bd0b0a5312a6fedb49c1bbd88fd75d8b9a09486c60123dfa1d73c1cc3169761b17476d3c6b7cbbd727acd0e2c942f4dd96ae3da5de368d26b32286e32de7e5a8cb2949f866a0b80c58116b29fa7fabb3ea7d520ee603e0c25bcaf0b9a5e92ec6a1fe4e0391d1cdbce8c68a"),
+            (b"a512_aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa", 0x80, "546aff5444b5b79aa6148bd81728704c32decb73a3ba76e9e75885cad9def1d06d6792f8a7d12794e90efed817d96920d728896a4510864370c207f99bd4a608ea121700ef01ed879745ee3e4ceef777eda6d9e5e38b90c86ea6fb0b36504ba4a45d22e86f6db5dd43d98a294bebb9125d5b794e9d2a81181066eb954966a487"),
+        ];
+
+        for (msg, len_in_bytes, expected) in vectors {
+            let uniform_bytes = expand_message_xmd(msg, dst, len_in_bytes).unwrap();
+            assert_eq!(hex::encode(uniform_bytes), expected);
+        }
+    }
+
+    #[test]
+    fn test_expand_message_xmd_errors() {
+        let dst = b"QUUX-V01-CS02-with-expander-SHA256-128";
+        assert!(matches!(expand_message_xmd(b"abc", b"", 32), Err(ExpandError::InvalidDst)));
+        assert!(matches!(expand_message_xmd(b"abc", dst, 255 * 32 + 1), Err(ExpandError::OutputTooLarge)));
+        assert_eq!(expand_message_xmd(b"abc", dst, 255 * 32).unwrap().len(), 255 * 32);
+        assert!(matches!(expand_message_xmd(b"abc", dst, u16::MAX as usize + 1), Err(ExpandError::OutputTooLarge)));
+        assert!(matches!(expand_message_xmd_sha512(b"abc", dst, u16::MAX as usize + 1), Err(ExpandError::OutputTooLarge)));
+    }
+
+    #[test]
+    fn test_expand_message_xmd_max_len() {
+        let dst = b"QUUX-V01-CS02-with-expander-SHA256-128";
+
+        // ell = 255 is the largest expansion SHA-256 allows
+        let uniform_bytes = expand_message_xmd(b"abc", dst, 8160).unwrap();
+        assert_eq!(hex::encode(&uniform_bytes[..32]), "6d2c62f8b7432449fe5475c081dc5f1ea2b53c5b8a836eb38e89d3d91f5e4abb");
+        assert_eq!(hex::encode(&uniform_bytes[8128..]), "7e774ebadea6c586b314d8032d47dc5354aa1a00330f78c32daf0b0ef245c777");
+
+        // A partial final block is a prefix of b_ell, not a wrap-around into earlier output
+        let uniform_bytes = expand_message_xmd(b"abc", dst, 8150).unwrap();
+        assert_eq!(hex::encode(&uniform_bytes[8128..]), "63221d82055a542470793b7eca089605dcc564d641d6");
+    }
+
+    #[test]
+    fn test_expand_message_oversize_dst() {
+        // RFC 9380 appendix K.1, long DST
+        let mut dst = b"QUUX-V01-CS02-with-expander-SHA256-128-long-DST-".to_vec();
+        dst.resize(256, b'1');
+        let uniform_bytes = expand_message_xmd(b"", &dst, 0x20).unwrap();
+        assert_eq!(hex::encode(uniform_bytes), "e8dc0c8b686b7ef2074086fbdd2f30e3f8bfbd3bdf177f73f04b97ce618a3ed3");
+
+        // A 300 byte DST, against an independent Python implementation of the RFC
+        let dst = [b'A'; 300];
+        assert_eq!(hex::encode(expand_message_xmd(b"abc", &dst, 0x20).unwrap()), "8322c31fc6d942958fcd49d4a85b60cee5f4db6ec70ade794112bbee61939d32");
+        assert_eq!(hex::encode(expand_message_xof_shake128(b"abc", &dst, 0x20).unwrap()), "f8a65633980bab383e84623c585985286491c0719c4ed2c6d9ee71d5dbedda71");
+        assert_eq!(hex::encode(expand_message_xof_shake256(b"abc", &dst, 0x20).unwrap()), "dc6bd17be9ffa068f0d138d8b8f537becb1bdd395762695c580dc0eeb3122494");
+
+        // Equivalent to passing the reduced DST directly
+        let reduced = Sha256::digest([&b"H2C-OVERSIZE-DST-"[..], &dst].concat());
+        assert_eq!(expand_message_xmd(b"abc", &dst, 0x20).unwrap(), expand_message_xmd(b"abc", &reduced, 0x20).unwrap());
+    }
+
+    #[test]
+    fn test_expand_message_xmd_binds_length() {
+        let dst = b"QUUX-V01-CS02-with-expander-SHA256-128";
+        let short = expand_message_xmd(b"abc", dst, 32).unwrap();
+        let long = expand_message_xmd(b"abc", dst, 64).unwrap();
+        assert_ne!(short[..], long[..32]);
+    }
+
+    #[test]
+    fn test_expand_message_xmd_sha256_alias() {
+        let dst = b"QUUX-V01-CS02-with-expander-SHA256-128";
+        assert_eq!(expand_message_xmd_sha256(b"abc", dst, 0x80).unwrap(), expand_message_xmd(b"abc", dst, 0x80).unwrap());
+    }
+
+    // The swap must not change any output: the patched hasher against the stock one
+    #[cfg(feature = "sp1")]
+    #[test]
+    fn test_sp1_sha256_matches() {
+        let dst = b"QUUX-V01-CS02-with-expander-SHA256-128";
+        for (msg, len) in [(&b""[..], 0x20), (b"abc", 0x80), (&[b'a'; 512][..], 0x80)] {
+            assert_eq!(expand_message_xmd(msg, dst, len).unwrap(), expand_message_xmd_with::<sha2::Sha256>(msg, dst, len).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_expand_message_xmd_sha512() {
+        // Test vectors taken from https://www.rfc-editor.org/rfc/rfc9380.html#appendix-K.3
+        let dst = b"QUUX-V01-CS02-with-expander-SHA512-256";
+
+        let vectors: [(&[u8], usize, &str); 6] = [
+            (b"", 0x20, "6b9a7312411d92f921c6f68ca0b6380730a1a4d982c507211a90964c394179ba"),
+            (b"abc", 0x20, "0da749f12fbe5483eb066a5f595055679b976e93abe9be6f0f6318bce7aca8dc"),
+            (b"abcdef0123456789", 0x20, "087e45a86e2939ee8b91100af1583c4938e0f5fc6c9db4b107b83346bc967f58"),
+            (b"", 0x80, "41b037d1734a5f8df225dd8c7de38f851efdb45c372887be655212d07251b921b052b62eaed99b46f72f2ef4cc96bfaf254ebbbec091e1a3b9e4fb5e5b619d2e0c5414800a1d882b62bb5cd1778f098b8eb6cb399d5d9d18f5d5842cf5d13d7eb00a7cff859b605da678b318bd0e65ebff70bec88c753b159a805d2c89c55961"),
+            (b"abc", 0x80, "7f1dddd13c08b543f2e2037b14cefb255b44c83cc397c1786d975653e36a6b11bdd7732d8b38adb4a0edc26a0cef4bb45217135456e58fbca1703cd6032cb1347ee720b87972d63fbf232587043ed2901bce7f22610c0419751c065922b488431851041310ad659e4b23520e1772ab29dcdeb2002222a363f0c2b1c972b3efe1"),
+            (b"abcdef0123456789", 0x80, "3f721f208e6199fe903545abc26c837ce59ac6fa45733f1baaf0222f8b7acb0424814fcb5eecf6c1d38f06e9d0a6ccfbf85ae612ab8735dfdf9ce84c372a77c8f9e1c1e952c3a61b7567dd0693016af51d2745822663d0c2367e3f4f0bed827feecc2aaf98c949b5ed0d35c3f1023d64ad1407924288d366ea159f46287e61ac"),
+        ];
+
+        for (msg, len_in_bytes, expected) in vectors {
+            let uniform_bytes = expand_message_xmd_sha512(msg, dst, len_in_bytes).unwrap();
+            assert_eq!(hex::encode(uniform_bytes), expected);
+        }
+    }
+
+    #[test]
+    fn test_expand_message_xmd_sha384() {
+        let dst = b"QUUX-V01-CS02-with-expander-SHA384-192";
+
+        let vectors: [(&[u8], usize, &str); 4] = [
+            (b"", 0x20, "340428e4bce14a7d765f903c27ed08e29e3b57e07d8134d4a638bdf7a0a78666"),
+            (b"abc", 0x20, "d8919e0b1ffaf41148e1f32728cd4b135787f8b33489cd64b09371556bcdfcbc"),
+            (b"", 0x80, "e41ebb2efd2358466f5c4168db3b1f289ace6868af9205d8eaec1f178200b12cf079b306c67a1f716980d8a9f49040a0b7bf7858475fef1ab9d64640a20c510cc7da1808faab721bd7ef0379bced4fcf0fa193269ad37e8e7736f047b825fb191c8dae6bc64d72efaa87128895f4d0e497d040d41096661b5aa01466015026e4"),
+            (b"abc", 0x80, "bcca093ba3ac1912a5a525b2273ab4615b7c3e21fc96b44deb6db088430ccbf7ca49037aa46b825915640c055217dc0a73953fa4293ec389b7860d875febf763c028da78793677e200896521b94c24b54e3567139920e5fc32224c92377579cdb56394f7e02a55d94149c49df1908c6608035a73b0ba7cd052efec36f5e34283"),
+        ];
+
+        for (msg, len_in_bytes, expected) in vectors {
+            let uniform_bytes = expand_message_xmd_sha384(msg, dst, len_in_bytes).unwrap();
+            assert_eq!(hex::encode(uniform_bytes), expected);
+        }
+    }
+
+    #[test]
+    fn test_expand_message_xof_shake128() {
+        // Test vectors taken from https://www.rfc-editor.org/rfc/rfc9380.html#appendix-K.4
+        let dst = b"QUUX-V01-CS02-with-expander-SHAKE128";
+
+        let vectors: [(&[u8], usize, &str); 6] = [
+            (b"", 0x20, "86518c9cd86581486e9485aa74ab35ba150d1c75c88e26b7043e44e2acd735a2"),
+            (b"abc", 0x20, "8696af52a4d862417c0763556073f47bc9b9ba43c99b505305cb1ec04a9ab468"),
+            (b"abcdef0123456789", 0x20, "912c58deac4821c3509dbefa094df54b34b8f5d01a191d1d3108a2c89077acca"),
+            (b"", 0x80, "7314ff1a155a2fb99a0171dc71b89ab6e3b2b7d59e38e64419b8b6294d03ffee42491f11370261f436220ef787f8f76f5b26bdcd850071920ce023f3ac46847744f4612b8714db8f5db83205b2e625d95afd7d7b4d3094d3bdde815f52850bb41ead9822e08f22cf41d615a303b0d9dde73263c049a7b9898208003a739a2e57"),
+            (b"abc", 0x80, "c952f0c8e529ca8824acc6a4cab0e782fc3648c563ddb00da7399f2ae35654f4860ec671db2356ba7baa55a34a9d7f79197b60ddae6e64768a37d699a78323496db3878c8d64d909d0f8a7de4927dcab0d3dbbc26cb20a49eceb0530b431cdf47bc8c0fa3e0d88f53b318b6739fbed7d7634974f1b5c386d6230c76260d5337a"),
+            (b"abcdef0123456789", 0x80, "19b65ee7afec6ac06a144f2d6134f08eeec185f1a890fe34e68f0e377b7d0312883c048d9b8a1d6ecc3b541cb4987c26f45e0c82691ea299b5e6889bbfe589153016d8131717ba26f07c3c14ffbef1f3eff9752e5b6183f43871a78219a75e7000fbac6a7072e2b83c790a3a5aecd9d14be79f9fd4fb180960a3772e08680495"),
+        ];
+
+        for (msg, len_in_bytes, expected) in vectors {
+            let uniform_bytes = expand_message_xof_shake128(msg, dst, len_in_bytes).unwrap();
+            assert_eq!(hex::encode(uniform_bytes), expected);
+        }
+    }
+
+    #[test]
+    fn test_expand_message_xof_shake256() {
+        // Test vectors taken from https://www.rfc-editor.org/rfc/rfc9380.html#appendix-K.5
+        let dst = b"QUUX-V01-CS02-with-expander-SHAKE256";
+
+        let vectors: [(&[u8], usize, &str); 6] = [
+            (b"", 0x20, "2ffc05c48ed32b95d72e807f6eab9f7530dd1c2f013914c8fed38c5ccc15ad76"),
+            (b"abc", 0x20, "b39e493867e2767216792abce1f2676c197c0692aed061560ead251821808e07"),
+            (b"abcdef0123456789", 0x20, "245389cf44a13f0e70af8665fe5337ec2dcd138890bb7901c4ad9cfceb054b65"),
+            (b"", 0x80, "7a1361d2d7d82d79e035b8880c5a3c86c5afa719478c007d96e6c88737a3f631dd74a2c88df79a4cb5e5d9f7504957c70d669ec6bfedc31e01e2bacc4ff3fdf9b6a00b17cc18d9d72ace7d6b81c2e481b4f73f34f9a7505dccbe8f5485f3d20c5409b0310093d5d6492dea4e18aa6979c23c8ea5de01582e9689612afbb353df"),
+            (b"abc", 0x80, "a54303e6b172909783353ab05ef08dd435a558c3197db0c132134649708e0b9b4e34fb99b92a9e9e28fc1f1d8860d85897a8e021e6382f3eea10577f968ff6df6c45fe624ce65ca25932f679a42a404bc3681efe03fcd45ef73bb3a8f79ba784f80f55ea8a3c367408f30381299617f50c8cf8fbb21d0f1e1d70b0131a7b6fbe"),
+            (b"abcdef0123456789", 0x80, "e42e4d9538a189316e3154b821c1bafb390f78b2f010ea404e6ac063deb8c0852fcd412e098e231e43427bd2be1330bb47b4039ad57b30ae1fc94e34993b162ff4d695e42d59d9777ea18d3848d9d336c25d2acb93adcad009bcfb9cde12286df267ada283063de0bb1505565b2eb6c90e31c48798ecdc71a71756a9110ff373"),
+        ];
+
+        for (msg, len_in_bytes, expected) in vectors {
+            let uniform_bytes = expand_message_xof(msg, dst, len_in_bytes, XofVariant::Shake256).unwrap();
+            assert_eq!(hex::encode(uniform_bytes), expected);
+        }
+    }
+}
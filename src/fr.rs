@@ -0,0 +1,123 @@
+use alloc::vec::Vec;
+
+use substrate_bn::Fr;
+
+use crate::{expand_message_xmd, HashToCurveError, HashToField, LEN_PER_ELM};
+
+// `Fr` has no `from_be_bytes_mod_order`, so big-endian input is read as 16 byte limbs, each below r
+// and so canonical on its own, and recombined in the field as (a·2¹²⁸ + b)·2¹²⁸ + c. The limbs
+// are cut from the least significant end, so only the leading one can be short.
+// This replaced a num-bigint reduction, which was the crate's only use of it
+pub(crate) fn reduce_mod_r(bytes: &[u8]) -> Fr {
+    let mut shift = [0u8; 32];
+    shift[15] = 1;
+    let shift = Fr::from_slice(&shift).unwrap();
+
+    bytes.rchunks(16).rev().fold(Fr::zero(), |acc, limb| {
+        let mut buf = [0u8; 32];
+        buf[32 - limb.len()..].copy_from_slice(limb);
+        acc * shift + Fr::from_slice(&buf).unwrap()
+    })
+}
+
+// hash_to_field over the scalar field, for Fiat-Shamir challenges and blinding factors. Same
+// construction as gnark-crypto's fr.Hash; L = 48 also holds for r, which is the same bit length as p
+impl HashToField for Fr {
+    fn hash_to_field(msg: &[u8], dst: &[u8], count: usize) -> Result<Vec<Fr>, HashToCurveError> {
+        let uniform_bytes = expand_message_xmd(msg, dst, count * LEN_PER_ELM)?;
+        Ok(uniform_bytes.chunks(LEN_PER_ELM).map(reduce_mod_r).collect())
+    }
+}
+
+/// Single scalar from `msg`. Panics if `dst` is empty, like `HashToCurve::hash`
+pub fn hash_to_fr(msg: &[u8], dst: &[u8]) -> Fr {
+    hash_to_fr_many(msg, dst, 1)[0]
+}
+
+/// `count` independent scalars from one expansion. Panics if `dst` is empty or `count` is above
+/// 170, the most one expansion can produce; code sized by untrusted input should call
+/// `Fr::hash_to_field` and handle the error instead
+pub fn hash_to_fr_many(msg: &[u8], dst: &[u8], count: usize) -> Vec<Fr> {
+    Fr::hash_to_field(msg, dst, count).expect("Failed to hash to Fr")
+}
+
+// One scalar per index, for batch weights. A single expansion stops at 255 blocks, 170 scalars, so
+// `msg` is first compressed to a 32-byte digest and each index is hashed with it separately
+pub(crate) fn hash_to_fr_indexed(msg: &[u8], dst: &[u8], count: usize) -> Result<Vec<Fr>, HashToCurveError> {
+    let digest = expand_message_xmd(msg, dst, 32)?;
+    (0..count as u64)
+        .map(|i| Ok(Fr::hash_to_field(&[&digest[..], &i.to_be_bytes()].concat(), dst, 1)?[0]))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use num_bigint::BigUint;
+
+    // Order of the BN254 scalar field
+    const R: &str = "21888242871839275222246405745257275088548364400416034343698204186575808495617";
+
+    #[test]
+    fn test_hash_to_fr() {
+        let u = hash_to_fr_many(b"abc", b"QUUX-V01-CS02-with-BN254FR_XMD:SHA-256_", 2);
+        assert!(u[0] == Fr::from_str("18644600981463069482109419002798370322347678510599311804348346072048435646791").unwrap());
+        assert!(u[1] == Fr::from_str("15032281091509819015624538599184555824899102601066340440893291718076860144563").unwrap());
+    }
+
+    #[test]
+    fn test_reduce_mod_r() {
+        let r = BigUint::parse_bytes(R.as_bytes(), 10).unwrap();
+        let to_bytes = |n: BigUint| {
+            let b = n.to_bytes_be();
+            let mut out = vec![0u8; LEN_PER_ELM - b.len()];
+            out.extend(b);
+            out
+        };
+
+        assert!(reduce_mod_r(&to_bytes(r.clone())) == Fr::zero());
+        assert!(reduce_mod_r(&to_bytes(&r + 1u32)) == Fr::one());
+        assert!(reduce_mod_r(&to_bytes(&r * 2u32 + 5u32)) == Fr::from_str("5").unwrap());
+        assert!(reduce_mod_r(&to_bytes(&r - 1u32)) == -Fr::one());
+
+        // The largest 48 byte input, checked against an independent big-integer reduction
+        let max = BigUint::from_bytes_be(&[0xff; LEN_PER_ELM]);
+        assert!(reduce_mod_r(&[0xff; LEN_PER_ELM]) == Fr::from_str(&(max % &r).to_str_radix(10)).unwrap());
+    }
+
+    #[test]
+    fn test_hash_to_fr_dst_separation() {
+        let a = hash_to_fr_many(b"abc", b"DST-A", 4);
+        let b = hash_to_fr_many(b"abc", b"DST-B", 4);
+        assert!(a.iter().zip(&b).all(|(x, y)| x != y));
+    }
+
+    #[test]
+    fn test_hash_to_fr_single() {
+        let dst = b"QUUX-V01-CS02-with-BN254FR_XMD:SHA-256_";
+        // len_in_bytes is part of the expansion, so one scalar is not a prefix of two
+        assert!(hash_to_fr(b"abc", dst) == Fr::hash_to_field(b"abc", dst, 1).unwrap()[0]);
+        assert!(hash_to_fr(b"abc", dst) != hash_to_fr_many(b"abc", dst, 2)[0]);
+        assert!(matches!(Fr::hash_to_field(b"abc", b"", 1), Err(HashToCurveError::InvalidDst)));
+    }
+
+    #[test]
+    #[should_panic(expected = "Failed to hash to Fr")]
+    fn test_hash_to_fr_many_limit() {
+        assert_eq!(hash_to_fr_many(b"abc", b"DST-A", 170).len(), 170);
+        hash_to_fr_many(b"abc", b"DST-A", 171);
+    }
+
+    #[test]
+    fn test_hash_to_fr_indexed() {
+        let dst = b"DST-A";
+        let u = hash_to_fr_indexed(b"abc", dst, 200).unwrap();
+        assert_eq!(u.len(), 200);
+        assert!(u == hash_to_fr_indexed(b"abc", dst, 200).unwrap());
+        // Each scalar depends only on its index, so a shorter batch is a prefix
+        assert!(u[..3] == hash_to_fr_indexed(b"abc", dst, 3).unwrap()[..]);
+        assert!(u[0] != u[1]);
+        assert!(u[0] != hash_to_fr_indexed(b"abd", dst, 1).unwrap()[0]);
+        assert!(Fr::hash_to_field(b"abc", dst, 171).is_err());
+    }
+}
@@ -1,6 +1,57 @@
-use substrate_bn::{arith::U256, AffineG2, Fq, Fq2, Fr, Group, G2};
+use alloc::vec::Vec;
 
-use crate::{g1::HashToField, HashToCurve};
+use substrate_bn::{arith::U256, AffineG2, Fq, Fq2, Fr, GroupError, G2};
+use once_cell::sync::Lazy;
+use subtle::{Choice, ConstantTimeEq};
+
+use crate::arith::{g2_add, g2_mul};
+use crate::{g1, HashToCurve, HashToCurveError, HashToField};
+
+fn fq2(c0: &str, c1: &str) -> Fq2 {
+    Fq2::new(Fq::from_str(c0).unwrap(), Fq::from_str(c1).unwrap())
+}
+
+// B' = 3 / (9 + i), the coefficient of the sextic twist y² = x³ + B'
+pub(crate) static B: Lazy<Fq2> = Lazy::new(|| fq2(
+    "19485874751759354771024239261021720505790618469301721065564631296452457478373",
+    "266929791119991161246907387137283842545076965332900288569378510910307636690",
+));
+
+// SVDW constants for the twist (A = 0), RFC 9380 section 6.6.1, in canonical (non-Montgomery)
+// form. g(x) = x³ + B'; `test_svdw_constants` recomputes each from its definition
+
+// Z = 1, as in gnark-crypto and the RFC 9380 BN254G2 suite
+static Z: Lazy<Fq2> = Lazy::new(Fq2::one);
+// c1 = g(Z) = 1 + B'
+static C1: Lazy<Fq2> = Lazy::new(|| fq2(
+    "19485874751759354771024239261021720505790618469301721065564631296452457478374",
+    "266929791119991161246907387137283842545076965332900288569378510910307636690",
+));
+// c2 = -Z / 2 = (p - 1) / 2
+static C2: Lazy<Fq2> = Lazy::new(|| fq2(
+    "10944121435919637611123202872628637544348155578648911831344518947322613104291",
+    "0",
+));
+// c3 = sqrt(-g(Z) * 3Z²), the root with sgn0(c3) = 0
+static C3: Lazy<Fq2> = Lazy::new(|| fq2(
+    "18992192239972082890849143911285057164064277369389217330423471574879236301292",
+    "21819008332247140148575583693947636719449476128975323941588917397607662637108",
+));
+// c4 = -4g(Z) / 3Z²
+static C4: Lazy<Fq2> = Lazy::new(|| fq2(
+    "10499238450719652342378357227399831140106360636427411350395554762472100376473",
+    "6940174569119770192419592065569379906172001098655407502803841283667998553941",
+));
+
+// psi coefficients: x is scaled by (9 + i)^((p - 1) / 3) and y by (9 + i)^((p - 1) / 2)
+static ENDO_U: Lazy<Fq2> = Lazy::new(|| fq2(
+    "21575463638280843010398324269430826099269044274347216827212613867836435027261",
+    "10307601595873709700152284273816112264069230130616436755625194854815875713954",
+));
+static ENDO_V: Lazy<Fq2> = Lazy::new(|| fq2(
+    "2821565182194536844548159561693502659359617185244120367078079554186484126554",
+    "3505843767911556378687030309984248845540243509899259641013678093033130930403",
+));
 
 trait Conjugate {
     fn conjugate(self) -> Self;
@@ -18,271 +69,573 @@ impl Conjugate for G2 {
     }
 }
 
-fn psi(a: &AffineG2) -> AffineG2 {
-    let a: G2 = (*a).into();
-    let mut p = G2::one();
+// psi on Jacobian coordinates: conjugation and the twist constants commute with the Z scaling
+// (x = X/Z², y = Y/Z³), so no affine conversion is needed between applications
+fn psi_projective(p: G2) -> G2 {
+    let mut p = p.conjugate();
+
+    p.set_x(p.x() * *ENDO_U);
+    p.set_y(p.y() * *ENDO_V);
 
-    let c0 = Fq::from_str("21575463638280843010398324269430826099269044274347216827212613867836435027261").unwrap();
-    let c1 = Fq::from_str("10307601595873709700152284273816112264069230130616436755625194854815875713954").unwrap();     
-    let endo_u = Fq2::new(c0, c1);
+    p
+}
 
-    let c0 = Fq::from_str("2821565182194536844548159561693502659359617185244120367078079554186484126554").unwrap();
-    let c1 = Fq::from_str("3505843767911556378687030309984248845540243509899259641013678093033130930403").unwrap();
-    let endo_v = Fq2::new(c0, c1);
+pub(crate) fn psi(a: &AffineG2) -> AffineG2 {
+    psi_projective((*a).into()).into()
+}
 
+// RFC 9380 hash_to_field with m = 2: expands count * 2 * L bytes and reads each extension
+// element as two consecutive base field elements, c0 first
+impl HashToField for Fq2 {
+    fn hash_to_field(msg: &[u8], dst: &[u8], count: usize) -> Result<Vec<Fq2>, HashToCurveError> {
+        let u = g1::hash_to_field(msg, dst, count * 2)?;
+        Ok(u.chunks(2).map(|c| Fq2::new(c[0], c[1])).collect())
+    }
+}
 
-    p = p.conjugate();
+// `Fq2` is foreign, so rather than `ConditionallySelectable` select each coordinate with the
+// limb-wise G1 helper. Returns `a` when `c` is 0 and `b` when it is 1
+pub(crate) fn cmov(a: Fq2, b: Fq2, c: Choice) -> Fq2 {
+    Fq2::new(g1::cmov(a.real(), b.real(), c), g1::cmov(a.imaginary(), b.imaginary(), c))
+}
 
-    p.set_x(p.x() * endo_u);
-    p.set_y(p.y() * endo_v);
+fn inv0(x: Fq2) -> Fq2 {
+    x.inverse().unwrap_or(Fq2::zero())
+}
+
+impl g1::Inv0 for Fq2 {
+    fn one() -> Self {
+        Fq2::one()
+    }
+
+    fn zero() -> Self {
+        Fq2::zero()
+    }
+
+    fn is_zero(&self) -> bool {
+        Fq2::is_zero(self)
+    }
+
+    fn inv0(self) -> Self {
+        inv0(self)
+    }
+}
 
-    p.into()
+fn sqrt(x: Fq2) -> (Fq2, Choice) {
+    let y = x.sqrt();
+    (y.unwrap_or(Fq2::zero()), Choice::from(y.is_some() as u8))
 }
 
-// https://github.com/Consensys/gnark-crypto/blob/master/ecc/bn254/g2.go#L635
-fn clear_cofactor(q: AffineG2) -> AffineG2 {
-    const X_GEN: u64 = 4965661367192848881;
+// Steps 1-4 of the SVDW map. tv3 = inv0(tv1 * tv2) is the map's only inversion, an Fq2 one here,
+// so `map_to_curve_batch` shares it across inputs
+fn svdw_denominator(u: Fq2) -> (Fq2, Fq2) {
+    let tv1 = u * u * *C1;                      //    1-2. tv1 = u² * c1
+    (Fq2::one() - tv1, Fq2::one() + tv1)        //    3-4. tv2 = 1 + tv1, tv1 = 1 - tv1
+}
 
-    let mut points = [AffineG2::one();4];
+// The rest of the SVDW map, given tv3 = inv0(tv1 * tv2)
+fn svdw_finish(u: Fq2, tv1: Fq2, tv2: Fq2, tv3: Fq2) -> Result<AffineG2, HashToCurveError> {
+    svdw_finish_with(u, tv1, tv2, tv3, sqrt)
+}
 
-    let x_gen_scalar = Fr::new(U256::from(X_GEN)).unwrap();
+// `svdw_finish` over the given square root, so tests can count the calls
+fn svdw_finish_with(
+    u: Fq2,
+    tv1: Fq2,
+    tv2: Fq2,
+    tv3: Fq2,
+    mut sqrt: impl FnMut(Fq2) -> (Fq2, Choice),
+) -> Result<AffineG2, HashToCurveError> {
+    let (z, c2, c3, c4) = (*Z, *C2, *C3, *C4);
+    let b = *B;
+
+    let mut tv4 = u * tv1;          //    7.  tv4 = u * tv1
+    tv4 = tv4 * tv3;                //    8.  tv4 = tv4 * tv3
+    tv4 = tv4 * c3;                 //    9.  tv4 = tv4 * c3
+    let x1 = c2 - tv4;              //    10.  x1 = c2 - tv4
+
+    let mut gx1 = x1 * x1;      //    11. gx1 = x1²
+    //12. gx1 = gx1 + A     All curves in gnark-crypto have A=0 (j-invariant=0). It is crucial to include this step if the curve has nonzero A coefficient.
+    gx1 = gx1 * x1;                 //    13. gx1 = gx1 * x1
+    gx1 = gx1 + b;              //    14. gx1 = gx1 + B
+
+    let x2 = c2 + tv4;              //    15.  x2 = c2 + tv4
+    let mut gx2 = x2 * x2;      //    16. gx2 = x2²
+    //    17. gx2 = gx2 + A (see 12.)
+    gx2 = gx2 * x2;                 //    18. gx2 = gx2 * x2
+    gx2 = gx2 + b;              //    19. gx2 = gx2 + B
+
+    let mut x3 = tv2 * tv2;      //    20.  x3 = tv2²
+    x3 = x3 * tv3;                  //    21.  x3 = x3 * tv3
+    x3 = x3 * x3;               //    22.  x3 = x3²
+    x3 = x3 * c4;                   //    23.  x3 = x3 * c4
+
+    x3 = x3 + z;                    //    24.  x3 = x3 + Z
+
+    let mut gx3 = x3 * x3;
+    gx3 = gx3 * x3;
+    gx3 = gx3 + b;
+
+    // As in G1, each candidate's square root doubles as its is_square check
+    let (y1, e1) = sqrt(gx1);
+    let (y2, e2) = sqrt(gx2);
+    let (y3, e3) = sqrt(gx3);
+    if !bool::from(e1 | e2 | e3) {
+        return Err(GroupError::NotOnCurve.into());
+    }
 
-    points[0] = (G2::from(q) * x_gen_scalar).into();
+    let e2 = e2 & !e1;
+    let mut x = cmov(x3, x1, e1);   //    25.   x = CMOV(x3, x1, e1)   # x = x1 if gx1 is square, else x = x3
+    x = cmov(x, x2, e2);            //    26.   x = CMOV(x, x2, e2)    # x = x2 if gx2 is square and gx1 is not
+    let mut y = cmov(y3, y1, e1);   //    31.   y = sqrt(gx)
+    y = cmov(y, y2, e2);
 
-    points[1] = (0..3).fold(G2::zero(), |acc, _| acc + points[0].into()).into();
-    points[1] = psi(&points[1]);
+    let e4 = Choice::from((AffineG2::sgn0(u) ^ AffineG2::sgn0(y)) as u8);  //    32.  e3 = sgn0(u) == sgn0(y)
+    let y = cmov(y, Fq2::zero() - y, e4);   //    33.   y = CMOV(-y, y, e3)       # Select correct sign of y
 
-    points[2] = psi(&points[0]);
-    points[2] = psi(&points[2]);
+    curve_point(x, y, b)
+}
 
-    points[3] = psi(&q);
-    points[3] = psi(&points[3]);
-    points[3] = psi(&points[3]);
+// `AffineG2::new` rejects points outside the r-torsion, but the SVDW output only lands there
+// after cofactor clearing, so check the curve equation against `b` here and build the point
+// from Jacobian coordinates
+fn curve_point(x: Fq2, y: Fq2, b: Fq2) -> Result<AffineG2, HashToCurveError> {
+    if y * y != x * x * x + b {
+        return Err(GroupError::NotOnCurve.into());
+    }
+    Ok(AffineG2::from_jacobian(G2::new(x, y, Fq2::one())).ok_or(GroupError::NotOnCurve)?)
+}
 
-    points.iter().fold(G2::zero(), |acc, point| acc + (*point).into()).into()
+/// `map_to_curve` over a slice with one shared Fq2 inversion; `hash` maps its two elements through it
+pub(crate) fn map_to_curve_batch(us: &[Fq2]) -> Result<Vec<AffineG2>, HashToCurveError> {
+    let tvs: Vec<(Fq2, Fq2)> = us.iter().map(|&u| svdw_denominator(u)).collect();
+    let invs = g1::batch_inv0(&tvs.iter().map(|&(tv1, tv2)| tv1 * tv2).collect::<Vec<_>>());
+    us.iter().zip(tvs).zip(invs).map(|((&u, (tv1, tv2)), tv3)| svdw_finish(u, tv1, tv2, tv3)).collect()
 }
 
 impl HashToCurve for AffineG2 {
     type FieldElement = Fq2;
+    const DST: &'static [u8] = b"QUUX-V01-CS02-with-BN254G2_XMD:SHA-256_SVDW_RO_";
 
+    // sgn0 for m = 2: the sign of c1 only counts when c0 is exactly zero, which needs both limbs
     fn sgn0(u: Fq2) -> u64 {
-        let mut sign = 0u64;
-        let mut zero = 1u64;
+        let t0 = u.real().into_u256();
+        let t1 = u.imaginary().into_u256();
 
+        let sign_0 = Choice::from((t0.0[0] & 1) as u8);
+        let zero_0 = (t0.0[0] | t0.0[1]).ct_eq(&0);
+        let sign_1 = Choice::from((t1.0[0] & 1) as u8);
 
-        let t: U256 = u.real().into_u256();
-        let t0 = t.get_bit(0).unwrap();
+        (sign_0 | (zero_0 & sign_1)).unwrap_u8() as u64
+    }
+    
+    // https://github.com/Consensys/gnark-crypto/blob/master/ecc/bn254/g2.go#L635
+    // Fuentes-Castañeda et al.: [x]Q + ψ([3x]Q) + ψ²([x]Q) + ψ³(Q), sharing [x]Q and staying
+    // in Jacobian coordinates until the final sum. Callable on any twist point as
+    // `AffineG2::clear_cofactor`; the result is always in the prime-order subgroup
+    fn clear_cofactor(q: AffineG2) -> AffineG2 {
+        const X_GEN: u64 = 4965661367192848881;
+
+        let q = G2::from(q);
+        let xq = g2_mul(q, Fr::new(U256::from(X_GEN)).unwrap());
+        let xq2 = g2_add(xq, xq);
+
+        let t1 = psi_projective(g2_add(xq2, xq));
+        let t2 = psi_projective(psi_projective(xq));
+        let t3 = psi_projective(psi_projective(psi_projective(q)));
+
+        g2_add(g2_add(xq, t1), g2_add(t2, t3)).into()
+    }
 
-        let mut sign_i = t0 as u64 & 1;
-        let zero_i = ((t.0[0] as u64) == 0) as u64;
+    fn map_to_curve(u: Fq2) -> Result<Self, HashToCurveError> {
+        let (tv1, tv2) = svdw_denominator(u);
+        svdw_finish(u, tv1, tv2, inv0(tv1 * tv2))  //    5-6. tv3 = inv0(tv1 * tv2)
+    }
 
-        sign = sign | (zero & sign_i);
-        zero = zero & zero_i;
+    fn try_hash(msg: &[u8], dst: &[u8]) -> Result<Self, HashToCurveError> {
+        let u = Fq2::hash_to_field(msg, dst, 2)?;
 
-        let t: U256 = u.imaginary().into_u256();
-        sign_i = t.0[0] as u64 & 1;
-        sign = sign | (zero & sign_i);
+        let q = map_to_curve_batch(&u)?;
 
-        sign
+        Ok(Self::clear_cofactor(g2_add(q[0].into(), q[1].into()).into()))
     }
-    
-    fn map_to_curve(u: Fq2) -> Result<Self, substrate_bn::GroupError> {
-        let z = Fq2::new(
-            Fq::from_str("6350874878119819312338956282401532409788428879151445726012394534686998597021").unwrap(),
-            Fq::from_str("0").unwrap()
-        );
 
-        let c1 = Fq2::new(
-            Fq::from_str("1234912246041461878588942434875861039904126177810565185887158306408069993214").unwrap(), 
-            Fq::from_str("568440292453150825972223760836185707764922522371208948902804025364325400423").unwrap()
-        );
+    fn try_hash_batch<M: AsRef<[u8]>>(msgs: &[M], dst: &[u8]) -> Result<Vec<Self>, HashToCurveError> {
+        let mut us = Vec::with_capacity(2 * msgs.len());
+        for msg in msgs {
+            us.extend(Fq2::hash_to_field(msg.as_ref(), dst, 2)?);
+        }
+        let qs = map_to_curve_batch(&us)?;
+        Ok(qs.chunks(2).map(|q| Self::clear_cofactor(g2_add(q[0].into(), q[1].into()).into())).collect())
+    }
+}
 
-        let c2 = Fq2::new(
-            Fq::from_str("7768683996859727954953724731427871339453941139073188968338321679979113805781").unwrap(), 
-            Fq::from_str("0").unwrap()
-        );
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{thread_rng, Rng};
+    use substrate_bn::Group;
 
-        let c3 = Fq2::new(
-            Fq::from_str("14301738157933195389348253840724448307870907218086206201704502222609770096511").unwrap(), 
-            Fq::from_str("18766576807588938823931941816656094168356257905513364070341807858523241306211").unwrap()
-        );
+    fn random_point() -> AffineG2 {
+        (G2::one() * Fr::random(&mut thread_rng())).into()
+    }
 
-        let c4 = Fq2::new(
-            Fq::from_str("12945612253170900976712347250337035339258705867784462193943147521219390814770").unwrap(), 
-            Fq::from_str("21130322481901740787616774064142360811676414460802878397485299194159459008019").unwrap()
-        );
-        
-        let B = Fq2::new(
-            Fq::from_str("19485874751759354771024239261021720505790618469301721065564631296452457478373").unwrap(), 
-            Fq::from_str("266929791119991161246907387137283842545076965332900288569378510910307636690").unwrap()
-        );
+    #[test]
+    fn test_svdw_constants() {
+        // Recompute the constants from their definitions with Fq2 arithmetic
+        let three = Fq2::new(Fq::from_str("3").unwrap(), Fq::zero());
+        let four = Fq2::new(Fq::from_str("4").unwrap(), Fq::zero());
+        let nine_i = fq2("9", "1");
+        assert!(*B * nine_i == three);
+
+        let z = *Z;
+        let g_z = z * z * z + *B;
+        let three_z2 = three * z * z;
+        assert!(*C1 == g_z);
+        assert!(*C2 * (z + z) == Fq2::zero() - z);
+        assert!(*C3 * *C3 == Fq2::zero() - g_z * three_z2);
+        assert_eq!(AffineG2::sgn0(*C3), 0);
+        assert!(*C4 * three_z2 == Fq2::zero() - four * g_z);
+    }
 
+    #[test]
+    fn test_map_to_curve_batch() {
+        assert!(map_to_curve_batch(&[]).unwrap().is_empty());
+
+        // Random inputs, plus a zero that has to stay out of the shared inversion
+        let mut rng = thread_rng();
+        let mut us: Vec<Fq2> = (0..32).map(|_| Fq2::new(Fq::random(&mut rng), Fq::random(&mut rng))).collect();
+        us.insert(5, Fq2::zero());
+        let batch = map_to_curve_batch(&us).unwrap();
+        for (&u, q) in us.iter().zip(&batch) {
+            assert!(*q == AffineG2::map_to_curve(u).unwrap());
+        }
+
+        let xs = [Fq2::one(), Fq2::zero(), us[0]];
+        for (&x, inv) in xs.iter().zip(g1::batch_inv0(&xs)) {
+            assert!(inv == inv0(x));
+        }
+    }
 
-        let mut tv1 = u * u;       //    1.  tv1 = u²
+    #[test]
+    fn test_hash_batch() {
+        let msgs: [&[u8]; 3] = [b"", b"abc", b"abcdef0123456789"];
+        let q = AffineG2::hash_batch(&msgs, AffineG2::DST);
+        assert!(q.iter().zip(msgs).all(|(&q, msg)| q == AffineG2::hash(msg, AffineG2::DST)));
+    }
 
-        tv1 = tv1 * c1;                 //    2.  tv1 = tv1 * c1
+    #[test]
+    fn test_psi_is_additive() {
+        let p = random_point();
+        let q = random_point();
+        let sum: AffineG2 = (G2::from(p) + G2::from(q)).into();
 
-        let tv2 = Fq2::one() + tv1;       //    3.  tv2 = 1 + tv1
+        let lhs: G2 = psi(&sum).into();
+        let rhs = G2::from(psi(&p)) + G2::from(psi(&q));
+        assert!(lhs == rhs);
+    }
 
-        tv1 = Fq2::one() - tv1;           //    4.  tv1 = 1 - tv1
-        let mut tv3 = tv1 * tv2;        //    5.  tv3 = tv1 * tv2
+    #[test]
+    fn test_psi_uses_input() {
+        let p = random_point();
+        let q = random_point();
+        assert!(psi(&p) != psi(&q));
+        assert!(psi(&p) != psi(&AffineG2::one()));
+    }
 
-        tv3 = Fq2::one() / tv3;               //    6.  tv3 = inv0(tv3)
-        let mut tv4 = u * tv1;          //    7.  tv4 = u * tv1
-        tv4 = tv4 * tv3;                //    8.  tv4 = tv4 * tv3
-        tv4 = tv4 * c3;                 //    9.  tv4 = tv4 * c3
-        let x1 = c2 - tv4;              //    10.  x1 = c2 - tv4
+    #[test]
+    fn test_psi_on_curve() {
+        for _ in 0..4 {
+            let q = psi(&random_point());
+            assert!(AffineG2::new(q.x(), q.y()).is_ok());
+        }
+    }
 
-        let mut gx1 = x1 * x1;      //    11. gx1 = x1²
-        //12. gx1 = gx1 + A     All curves in gnark-crypto have A=0 (j-invariant=0). It is crucial to include this step if the curve has nonzero A coefficient.
-        gx1 = gx1 * x1;                 //    13. gx1 = gx1 * x1
-        gx1 = gx1 + B;              //    14. gx1 = gx1 + B
+    // gnark-crypto prints field elements in Montgomery form (a * R mod p, R = 2^256); multiplying by
+    // R⁻¹ recovers the canonical value that `Fq::from_str` expects
+    fn from_mont(s: &str) -> Fq {
+        let r_inv = Fq::from_str("20988524275117001072002809824448087578619730785600314334253784976379291040311").unwrap();
+        Fq::from_str(s).unwrap() * r_inv
+    }
 
-        let x2 = c2 + tv4;              //    15.  x2 = c2 + tv4
-        let mut gx2 = x2 * x2;      //    16. gx2 = x2²
-        //    17. gx2 = gx2 + A (see 12.)
-        gx2 = gx2 * x2;                 //    18. gx2 = gx2 * x2
-        gx2 = gx2 + B;              //    19. gx2 = gx2 + B
+    #[test]
+    fn test_from_mont() {
+        // The constants this map used to carry were gnark's Montgomery form of Z = 1 and c1
+        assert!(from_mont("6350874878119819312338956282401532409788428879151445726012394534686998597021") == Fq::one());
+        let c1 = Fq2::new(
+            from_mont("1234912246041461878588942434875861039904126177810565185887158306408069993214"),
+            from_mont("568440292453150825972223760836185707764922522371208948902804025364325400423"),
+        );
+        assert!(c1 == fq2("19485874751759354771024239261021720505790618469301721065564631296452457478374", "266929791119991161246907387137283842545076965332900288569378510910307636690"));
+    }
+
+    // P * r == O, computed as P * (r - 1) + P since r itself is not a valid Fr
+    fn in_r_torsion(p: AffineG2) -> bool {
+        (G2::from(p) * (-Fr::one()) + G2::from(p)).is_zero()
+    }
 
-        let mut x3 = tv2 * tv2;      //    20.  x3 = tv2²
-        x3 = x3 * tv3;                  //    21.  x3 = x3 * tv3
-        x3 = x3 * x3;               //    22.  x3 = x3²
-        x3 = x3 * c4;                   //    23.  x3 = x3 * c4
+    #[test]
+    fn test_psi_vectors() {
+        // Values cross-checked against gnark-crypto's psi
+        let q = psi(&AffineG2::one());
+        assert!(q.x() == fq2("13824868563399673693405984206252027284526901521624614945388441201916943098448", "6070174842523651825461006324987645339257276059765462992338211551285097849152"));
+        assert!(q.y() == fq2("4224873494559498571787136390356590572898009346319218613936276445484292886657", "14979195929948718632567968180703131754953567972706796447883440492471033097811"));
 
-        x3 = x3 + z;                    //    24.  x3 = x3 + Z
+        let q = psi(&(G2::one() + G2::one()).into());
+        assert!(q.x() == fq2("18859311111551941396367495566158487655401966407473634651233945999661560802568", "11216147635980239206867696470677944546383931187376303723547412833638173421581"));
+        assert!(q.y() == fq2("11734015621193922805108018412086323524427644048958306795937942909463448385344", "17609580916527549550097218293614672273260858374128532889534868027834008357199"));
+
+        let q = psi(&AffineG2::hash(b"abc", b"QUUX-V01-CS02-with-BN254G2_XMD:SHA-256_SVDW_RO_"));
+        assert!(q.x() == fq2("7022825442845703245816403238088609761820430236444314303608974002217326317386", "6091070603783967869467288961356601614934800317250892385457042553976891417145"));
+        assert!(q.y() == fq2("2572688309228592224878830623028664730470651176480645063621224766658258645879", "7908773028785817908481129122525816368213529320866886213251957706348733152140"));
+    }
+
+    #[test]
+    fn test_clear_cofactor_lands_in_r_torsion() {
+        let mut rng = thread_rng();
+        for _ in 0..8 {
+            let u = Fq2::new(Fq::random(&mut rng), Fq::random(&mut rng));
+            let q = AffineG2::map_to_curve(u).unwrap();
+            assert!(in_r_torsion(AffineG2::clear_cofactor(q)));
+        }
+    }
 
-        let mut x = if gx1.sqrt().is_some() {x1} else {x3};   //    25.   x = CMOV(x3, x1, e1)   # x = x1 if gx1 is square, else x = x3
-        x = if gx2.sqrt().is_some() && !gx1.sqrt().is_some(){x2} else {x};      //    26.   x = CMOV(x, x2, e2)    # x = x2 if gx2 is square and gx1 is not
+    // Reference double-and-add by the full cofactor h = 2p - r, which does not fit in an Fr
+    fn mul_by_cofactor(p: AffineG2) -> G2 {
+        let h = U256([9049785074949702907797701415738669709, 64323764613183177041862057485226039390]);
+        (0..256).rev().fold(G2::zero(), |acc, i| {
+            let acc = acc + acc;
+            if h.get_bit(i).unwrap() { acc + G2::from(p) } else { acc }
+        })
+    }
 
-        let mut gx = x * x;        //    27.  gx = x²
-        //    28.  gx = gx + A
-        gx = gx * x;                    //    29.  gx = gx * x
-        gx = gx + B;    //    30.  gx = gx + B
+    #[test]
+    fn test_clear_cofactor_matches_full_cofactor() {
+        // ψ acts as [p] on G2, so the endomorphism chain equals [f(p) / h mod r] after [h]
+        let c = Fr::from_str("21888242871839275220042445260109153167277707414472061641744552603968177649903").unwrap();
+
+        let mut rng = thread_rng();
+        for _ in 0..4 {
+            let u = Fq2::new(Fq::random(&mut rng), Fq::random(&mut rng));
+            let q = AffineG2::map_to_curve(u).unwrap();
+            assert!(G2::from(AffineG2::clear_cofactor(q)) == mul_by_cofactor(q) * c);
+        }
+    }
 
-        let mut y = gx.sqrt().unwrap(); //    31.   y = sqrt(gx)
+    #[test]
+    fn test_clear_cofactor_vector() {
+        use crate::SubgroupCheck;
+
+        // Raw map_to_curve output from the gnark-crypto vectors in test_map_to_curve, cleared with
+        // the same formula as gnark-crypto's ClearCofactor
+        let u = fq2("15963713818282906360305918686195491545577210390832157279818305179904408824931", "2166278439352519416731010325104738631510195416620895094682522641528929475020");
+        let q = AffineG2::map_to_curve(u).unwrap();
+        assert!(!q.is_in_subgroup());
+
+        let q = AffineG2::clear_cofactor(q);
+        assert!(q == AffineG2::new(
+            fq2("10183213410607586156979914718752506954444932904377634485955579679470168687484", "6058522265067601270987803195152021740174932805984065427882220842522919233900"),
+            fq2("945460925614376188483816367497416617882694872917065055036910625870719540951", "11690418349561538596210146462840855878360320723314707736207790049707216430482"),
+        ).unwrap());
+        assert!(q.is_in_subgroup());
+    }
 
-        let signs_not_equal = Self::sgn0(u) ^ Self::sgn0(y);  //    32.  e3 = sgn0(u) == sgn0(y)
-        tv1 = Fq2::zero() - y;
+    #[test]
+    fn test_psi_projective_matches_affine() {
+        let p = G2::one() * Fr::from_str("12345").unwrap();
+        assert!(AffineG2::from(psi_projective(p)) == psi(&p.into()));
+    }
 
-        if signs_not_equal == 0 {y = y} else {y = tv1};   //    33.   y = CMOV(-y, y, e3)       # Select correct sign of y
+    #[test]
+    fn test_hash_lands_in_r_torsion() {
+        let mut rng = thread_rng();
+        for _ in 0..8 {
+            let msg: [u8; 16] = rng.gen();
+            let q = AffineG2::hash(&msg, b"QUUX-V01-CS02-with-BN254G2_XMD:SHA-256_SVDW_RO_");
+            assert!(in_r_torsion(q));
+            assert!(in_r_torsion(AffineG2::clear_cofactor(q)));
+        }
+    }
 
-        let res = AffineG2::new(x, y);
+    #[test]
+    fn test_sgn0() {
+        // c0 = 2^128 has a zero low limb but is not zero, so the sign of c1 must be ignored
+        assert_eq!(AffineG2::sgn0(fq2("340282366920938463463374607431768211456", "1")), 0);
+        assert_eq!(AffineG2::sgn0(fq2("1020847100762815390390123822295304634368", "2")), 0);
+
+        // Random elements checked against the RFC 9380 sgn0 reference definition
+        let cases = [
+            ("6215087815076330926179520016461010917137519558660815034878824735059242618923", "15951728188012883138265176510482648277956245750475693862477712774865526280408", 1),
+            ("18255373109897049727130802781095089727510501583111313296332426910213270751782", "1435512539167240917174679779456826558986830378504343210022207924205747402421", 0),
+            ("12133246360712595014301713218460443558891071268964820037424113512722450864844", "19742629340793347424684343855616647637539655466559710042175432972752183486116", 0),
+            ("2820422487248635544204436103609732825826976861705092992890896977555324409227", "14370217764983883853150210406407459395380230323068843817638056401039584197417", 1),
+            ("0", "9942941882774231512755382629142620981223457028423556949891709007769605315579", 1),
+            ("0", "14819443104185345886978595045022977331728925700401053624292392929989700152608", 0),
+        ];
+        for (c0, c1, sign) in cases {
+            assert_eq!(AffineG2::sgn0(fq2(c0, c1)), sign);
+        }
+    }
 
-        res
+    #[test]
+    fn test_hash2field() {
+        let u = Fq::hash_to_field(b"abc", b"QUUX-V01-CS02-with-BN254G2_XMD:SHA-256_SVDW_RO_", 4).unwrap();
+        assert!(u[0] == Fq::from_str("15963713818282906360305918686195491545577210390832157279818305179904408824931").unwrap());
+        assert!(u[1] == Fq::from_str("2166278439352519416731010325104738631510195416620895094682522641528929475020").unwrap());
+        assert!(u[2] == Fq::from_str("12752967732566665017975022503761080419696068755373050496264700974774108086129").unwrap());
+        assert!(u[3] == Fq::from_str("20655422394809824901799481664662586419100706577355794400212187554951433717414").unwrap());
     }
-    
-    fn hash(msg: &[u8], dst: &[u8]) -> Self {
-        let u = Fq::hash_to_field(msg, dst, 4);
 
-        let q0 = Self::map_to_curve(Fq2::new(u[0], u[1])).unwrap();
-        let q1 = Self::map_to_curve(Fq2::new(u[2], u[3])).unwrap();
+    #[test]
+    fn test_hash2field_fq2() {
+        let dst = b"QUUX-V01-CS02-with-BN254G2_XMD:SHA-256_SVDW_RO_";
+        let u = Fq2::hash_to_field(b"abc", dst, 2).unwrap();
+        assert!(u[0] == fq2("15963713818282906360305918686195491545577210390832157279818305179904408824931", "2166278439352519416731010325104738631510195416620895094682522641528929475020"));
+        assert!(u[1] == fq2("12752967732566665017975022503761080419696068755373050496264700974774108086129", "20655422394809824901799481664662586419100706577355794400212187554951433717414"));
+
+        // Same output as assembling the extension elements from four base field elements by hand
+        for msg in [&b""[..], b"abc", b"abcdef0123456789"] {
+            let v = Fq::hash_to_field(msg, dst, 4).unwrap();
+            let q0 = AffineG2::map_to_curve(Fq2::new(v[0], v[1])).unwrap();
+            let q1 = AffineG2::map_to_curve(Fq2::new(v[2], v[3])).unwrap();
+            let q = AffineG2::clear_cofactor((G2::from(q0) + G2::from(q1)).into());
+            assert!(AffineG2::hash(msg, dst) == q);
+        }
+    }
 
-        let q = [q0, q1].iter().fold(G2::zero(), |acc, &q| acc + q.into()).into();
-        
-        clear_cofactor(q)
+    #[test]
+    fn test_cmov() {
+        let a = fq2("15963713818282906360305918686195491545577210390832157279818305179904408824931", "2166278439352519416731010325104738631510195416620895094682522641528929475020");
+        let b = Fq2::zero() - a;
+        assert!(cmov(a, b, Choice::from(0)) == a);
+        assert!(cmov(a, b, Choice::from(1)) == b);
+        assert!(bool::from(sqrt(a * a).1));
     }
-}
 
-trait Print {
-    fn print(&self);
-}
+    #[test]
+    fn test_map_to_curve_exceptional() {
+        // Both square roots of ±1 / c1 make tv3 = 0, which leaves x = Z = 1
+        let u = fq2("6522986772542984542181201098928042101271211676799153813947561527442295110922", "7238107908531657737830469993684217640962042703142766184748324510739673750181");
+        let q = AffineG2::map_to_curve(u).unwrap();
+        assert!(q.x() == Fq2::one());
+        assert!(q.y() == fq2("3610091866386166428467545612961983990332663701371483510632385378352395651980", "15975588672102553735566230729081043132501226101599136527557730645158523614371"));
 
-impl Print for Fq {
-    fn print(&self) {
-        let mut bytes = [0u8; 32];
-        self.to_big_endian(&mut bytes).expect("Failed to convert Fq to big endian");
-        bytes.reverse();
-        println!("Fq bytes: {:?}", bytes);
+        let u = fq2("7238107908531657737830469993684217640962042703142766184748324510739673750181", "15365256099296290680065204646329232987425099480498669848741476367202931097661");
+        let q = AffineG2::map_to_curve(u).unwrap();
+        assert!(q.x() == Fq2::one());
+        assert!(q.y() == fq2("18278151005453108793778860132295291098363647455926340152056652516292830556603", "5912654199736721486680175016176231956195085055698687135131307249486702594212"));
+
+        assert!(inv0(Fq2::zero()) == Fq2::zero());
     }
-}
 
-impl Print for Fq2 {
-    fn print(&self) {
-        let mut real_bytes = [0u8; 32];
-        let mut imaginary_bytes = [0u8; 32];
+    #[test]
+    fn test_map_to_curve_sqrt_count() {
+        for msg in [&b""[..], b"abc", b"abcdef0123456789"] {
+            for u in Fq2::hash_to_field(msg, b"QUUX-V01-CS02-with-BN254G2_XMD:SHA-256_SVDW_RO_", 2).unwrap() {
+                let mut calls = 0;
+                let (tv1, tv2) = svdw_denominator(u);
+                let q = svdw_finish_with(u, tv1, tv2, inv0(tv1 * tv2), |x| {
+                    calls += 1;
+                    sqrt(x)
+                });
+                assert!(q.unwrap() == AffineG2::map_to_curve(u).unwrap());
+                assert_eq!(calls, 3);
+            }
+        }
+    }
 
-        self.real().to_big_endian(&mut real_bytes).expect("Failed to convert real part to big endian");
-        self.imaginary().to_big_endian(&mut imaginary_bytes).expect("Failed to convert imaginary part to big endian");
+    #[test]
+    fn test_map_to_curve_rejects_bad_constant() {
+        let u = fq2("15963713818282906360305918686195491545577210390832157279818305179904408824931", "2166278439352519416731010325104738631510195416620895094682522641528929475020");
 
-        real_bytes.reverse();
-        imaginary_bytes.reverse();
+        let q = AffineG2::map_to_curve(u).unwrap();
+        assert!(curve_point(q.x(), q.y(), *B).unwrap() == q);
 
-        println!("Fq2 real part bytes: {:?}", real_bytes);
-        println!("Fq2 imaginary part bytes: {:?}", imaginary_bytes);
+        // A wrong B' gives an error rather than a bad point
+        let q = curve_point(q.x(), q.y(), *B + Fq2::one());
+        assert!(matches!(q, Err(HashToCurveError::Group(GroupError::NotOnCurve))));
     }
-}
-impl Print for AffineG2 {
-    fn print(&self) {
-        self.x().print();
-        self.y().print();
+
+    #[test]
+    fn test_map_to_curve() {
+        // Test vectors taken from https://github.com/Consensys/gnark-crypto/blob/master/ecc/bn254/hash_vectors_test.go
+        let u = fq2("15963713818282906360305918686195491545577210390832157279818305179904408824931", "2166278439352519416731010325104738631510195416620895094682522641528929475020");
+        let q = AffineG2::map_to_curve(u).unwrap();
+        assert!(q.x() == fq2("16872093352184426853297847012752141646605261411290781565485515569233955899058", "20482288690411193526247554560661659739533735966007371008469181348051437821826"));
+        assert!(q.y() == fq2("427035866446275812154335387235552457760650543923113579505536211797911740485", "14849552243024588631071292176876897701191437999604860450422231174965236442203"));
+
+        let u = fq2("12752967732566665017975022503761080419696068755373050496264700974774108086129", "20655422394809824901799481664662586419100706577355794400212187554951433717414");
+        let q = AffineG2::map_to_curve(u).unwrap();
+        assert!(q.x() == fq2("12193882055337081757241417044229479753659926309860257758224177044622322698984", "10092155993942609715417531227866448864240630219985669320168414926220064901453"));
+        assert!(q.y() == fq2("21850450548984866542151665069165216760882062028063278212318726360439829725223", "10197523149668572844555341938160230574503097016636734560718180396672437043430"));
+
+        let u = fq2("18898141882839095816276844526801422247849121311000147859768000750276893266433", "3788127287937052767604234353437582991385298973804519256517508390161626404924");
+        let q = AffineG2::map_to_curve(u).unwrap();
+        assert!(q.x() == fq2("452805888478466390914725495219599183584561454657558688011312346353060651482", "7959928416860499659800248632934402218020177178560427800377197797165640390130"));
+        assert!(q.y() == fq2("14268098188884406522254505541441598455366967966015814006726862271011081843493", "15148517265986515293057552799755027217326970615601185424102524485888012383276"));
     }
-}
 
+    #[test]
+    fn test_hash2curve() {
+        let dst = b"QUUX-V01-CS02-with-BN254G2_XMD:SHA-256_SVDW_RO_";
+
+        let q = AffineG2::hash(b"abc", dst);
+        assert!(q == AffineG2::new(
+            fq2("10305213714312555419584685236164610766057227018997600762219755820581571775698", "5140998983273781645596043003996621170933075714207210952317183701750931672829"),
+            fq2("12782657610222102886506935265351398708799194735435757564502179253917869011884", "15746452850775091549966312821847336261590899319279618339578671846526379873840"),
+        ).unwrap());
+
+        let q = AffineG2::hash(b"", dst);
+        assert!(q == AffineG2::new(
+            fq2("7947280525355502288245767042139433332619084425813891508679326584140902765312", "10530141512348869141982713319207053343182583313484148698392330696376288318261"),
+            fq2("2079515028849057274649333561166551431956364880890028320215862191123161285080", "20169147323092870078028771345234445157617856249189458168875341276090072581620"),
+        ).unwrap());
+
+        let q = AffineG2::hash(b"abcdef0123456789", dst);
+        assert!(q == AffineG2::new(
+            fq2("9141649584568251133435811655082820452253999683001609355083509727807340928112", "19241337378620754008094815492162488101811979191715181531381201352430992486769"),
+            fq2("18149222514336885092356998491550186845822771992585824025266466238465484336696", "9129360097802525322055823374454170177267012396640126715240529872313988489338"),
+        ).unwrap());
+
+        let q = AffineG2::hash(b"q128_qqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqq", dst);
+        assert!(q == AffineG2::new(
+            fq2("20353650816686918912609727598093385895712524005202794071238544969713808081729", "17684256473523682464984867199875609280081365245056171175421469718260504681254"),
+            fq2("15896902550098660794387123920782326368527887924690142904247213645779094259076", "15390867031388969173331373188576779664345770454778413558467452103273727102977"),
+        ).unwrap());
+    }
 
-// #[cfg(test)]
-// mod tests {
-//     use super::*;
-
-//     #[test]
-//     fn test_map_to_curve() {
-//         let R_inv: Fq = Fq::from_slice(&[14, 10, 119, 193, 154, 7, 223, 47, 102, 110, 163, 111, 120, 121, 70, 44, 10, 120, 235, 40, 245, 199, 11, 61, 211, 93, 67, 141, 197, 143, 13, 157]).unwrap();
-
-//         let u = Fq2::new(
-//             Fq::from_str("15963713818282906360305918686195491545577210390832157279818305179904408824931").unwrap() * R_inv,
-//             Fq::from_str("2166278439352519416731010325104738631510195416620895094682522641528929475020").unwrap() * R_inv,
-//         );
-
-//         // let q = AffineG2::map_to_curve(u).unwrap();
-//         // let expected = AffineG2::new(Fq2::new(
-//         //     Fq::from_str("16872093352184426853297847012752141646605261411290781565485515569233955899058").unwrap(),
-//         //     Fq::from_str("20482288690411193526247554560661659739533735966007371008469181348051437821826").unwrap()
-//         // ), Fq2::new(
-//         //     Fq::from_str("427035866446275812154335387235552457760650543923113579505536211797911740485").unwrap(),
-//         //     Fq::from_str("14849552243024588631071292176876897701191437999604860450422231174965236442203").unwrap()
-//         // )).unwrap();
-//         // assert!(q == expected);
-
-//         // let u = Fq2::new(
-//         //     Fq::from_str("12752967732566665017975022503761080419696068755373050496264700974774108086129").unwrap(),
-//         //     Fq::from_str("20655422394809824901799481664662586419100706577355794400212187554951433717414").unwrap()
-//         // );
-
-//         // let q = AffineG2::map_to_curve(u).unwrap();
-//         // let expected = AffineG2::new(Fq2::new(
-//         //     Fq::from_str("12193882055337081757241417044229479753659926309860257758224177044622322698984").unwrap(),
-//         //     Fq::from_str("10092155993942609715417531227866448864240630219985669320168414926220064901453").unwrap()
-//         // ), Fq2::new(
-//         //     Fq::from_str("21850450548984866542151665069165216760882062028063278212318726360439829725223").unwrap(),
-//         //     Fq::from_str("10197523149668572844555341938160230574503097016636734560718180396672437043430").unwrap()
-//         // )).unwrap();
-//         // assert!(q == expected);
-
-//         // let u = Fq2::new(
-//         //     Fq::from_str("18898141882839095816276844526801422247849121311000147859768000750276893266433").unwrap(),
-//         //     Fq::from_str("3788127287937052767604234353437582991385298973804519256517508390161626404924").unwrap()
-//         // );
-
-//         // let q = AffineG2::map_to_curve(u).unwrap();
-//         // let expected = AffineG2::new(Fq2::new(
-//         //     Fq::from_str("452805888478466390914725495219599183584561454657558688011312346353060651482").unwrap(),
-//         //     Fq::from_str("7959928416860499659800248632934402218020177178560427800377197797165640390130").unwrap()
-//         // ), Fq2::new(
-//         //     Fq::from_str("14268098188884406522254505541441598455366967966015814006726862271011081843493").unwrap(),
-//         //     Fq::from_str("15148517265986515293057552799755027217326970615601185424102524485888012383276").unwrap()
-//         // )).unwrap();
-//         // assert!(q == expected);
-//     }    
-
-//     #[test]
-//     fn test_hash_to_curve() {
-//         let q = AffineG2::hash(b"abc", b"QUUX-V01-CS02-with-BN254G2_XMD:SHA-256_SVDW_RO_");
-//         let expected = AffineG2::new(Fq2::new(
-//             Fq::from_str("10305213714312555419584685236164610766057227018997600762219755820581571775698").unwrap(),
-//             Fq::from_str("5140998983273781645596043003996621170933075714207210952317183701750931672829").unwrap()
-//         ), Fq2::new(
-//             Fq::from_str("12782657610222102886506935265351398708799194735435757564502179253917869011884").unwrap(),
-//             Fq::from_str("15746452850775091549966312821847336261590899319279618339578671846526379873840").unwrap()
-//         )).unwrap();
-//         assert!(q == expected);
-//     }
-// }
\ No newline at end of file
+    #[test]
+    fn test_encode2curve() {
+        let dst = b"QUUX-V01-CS02-with-BN254G2_XMD:SHA-256_SVDW_NU_";
+
+        let q = AffineG2::encode(b"abc", dst);
+        assert!(q == AffineG2::new(
+            fq2("7290337032722028742894312496454770035215478865307401781131202361899492945880", "18605632812439984129247614998320701910992924251662446522071513278020164236983"),
+            fq2("18565926515830203734257806009639634340842708214357641080049757818108383758101", "21026435153745179081072575128771379049563093023676092614267505429710510687357"),
+        ).unwrap());
+
+        let q = AffineG2::encode(b"", dst);
+        assert!(q == AffineG2::new(
+            fq2("2222545202255207121622252341720884612662004487208664408317925491033383016781", "3167015911722190124689644160541231412539898594125261078778351544051685395067"),
+            fq2("20450065928984038040963910334909877834263207751235246619699259708122680403961", "4743914079645712786687283872900604142971897405422186449887746314931053675188"),
+        ).unwrap());
+
+        let q = AffineG2::encode(b"abcdef0123456789", dst);
+        assert!(q == AffineG2::new(
+            fq2("7148036967840401493869354348463445038937751410382870212181508408551260940454", "20374759774184409322905764368361574346849498692562411327726753719663647349306"),
+            fq2("10483260641720359876745669935893009958901176103433938324656495809668720301952", "4967329811281913502786824686629199594924414673725274625361393684486574196665"),
+        ).unwrap());
+
+        let q = AffineG2::encode(b"q128_qqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqq", dst);
+        assert!(q == AffineG2::new(
+            fq2("13125957534682971537993382516266248139111688303006779518993454727694490448781", "17001778660286232066011321802406169530508086950847889879278742058506410997887"),
+            fq2("21493575647416678969105094342755094397493417994062452257278523992404807814786", "17949331872970707337957877677444470806317301863003904868354967532347837920323"),
+        ).unwrap());
+
+        let q = AffineG2::encode(b"a512_aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa", dst);
+        assert!(q == AffineG2::new(
+            fq2("549777038834003445090108363667568008543458018879702424802185895608634070183", "17241878747514914600777537647804966909993790648100169250922892401106734291369"),
+            fq2("8654939251469409238390702955149995024930650667376373847357704134187240104302", "3048816029845140188911702407210291715056845238089676496464503410870814591366"),
+        ).unwrap());
+    }
+}
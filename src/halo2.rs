@@ -0,0 +1,176 @@
+use halo2curves::bn256;
+use halo2curves::ff::PrimeField;
+use halo2curves::group::prime::PrimeCurveAffine;
+use halo2curves::CurveAffine;
+use substrate_bn::{AffineG1, AffineG2, Fq, Fq2, Fr};
+
+use crate::DeserializationError;
+
+/// Converts to and from `halo2curves::bn256` types, the same way `Ark` does for arkworks.
+/// halo2curves stores elements as 32 little-endian bytes, so every element crosses as its canonical
+/// integer with the byte order reversed; limbs are never reinterpreted
+pub struct Halo2<T>(pub T);
+
+// Big-endian `substrate_bn` bytes to the little-endian repr halo2curves expects. Canonical on
+// both sides, so the repr is always in range
+fn from_be<F: PrimeField>(mut bytes: [u8; 32]) -> F {
+    bytes.reverse();
+    let mut repr = F::Repr::default();
+    repr.as_mut().copy_from_slice(&bytes);
+    F::from_repr(repr).unwrap()
+}
+
+fn to_be<F: PrimeField>(x: F) -> [u8; 32] {
+    let mut bytes = [0u8; 32];
+    bytes.copy_from_slice(x.to_repr().as_ref());
+    bytes.reverse();
+    bytes
+}
+
+impl From<Halo2<Fq>> for bn256::Fq {
+    fn from(x: Halo2<Fq>) -> Self {
+        let mut bytes = [0u8; 32];
+        x.0.to_big_endian(&mut bytes).unwrap();
+        from_be(bytes)
+    }
+}
+
+impl From<bn256::Fq> for Halo2<Fq> {
+    fn from(x: bn256::Fq) -> Self {
+        Halo2(Fq::from_slice(&to_be(x)).unwrap())
+    }
+}
+
+impl From<Halo2<Fr>> for bn256::Fr {
+    fn from(x: Halo2<Fr>) -> Self {
+        let mut bytes = [0u8; 32];
+        x.0.to_big_endian(&mut bytes).unwrap();
+        from_be(bytes)
+    }
+}
+
+impl From<bn256::Fr> for Halo2<Fr> {
+    fn from(x: bn256::Fr) -> Self {
+        Halo2(Fr::from_slice(&to_be(x)).unwrap())
+    }
+}
+
+impl From<Halo2<Fq2>> for bn256::Fq2 {
+    fn from(x: Halo2<Fq2>) -> Self {
+        bn256::Fq2 { c0: Halo2(x.0.real()).into(), c1: Halo2(x.0.imaginary()).into() }
+    }
+}
+
+impl From<bn256::Fq2> for Halo2<Fq2> {
+    fn from(x: bn256::Fq2) -> Self {
+        Halo2(Fq2::new(Halo2::from(x.c0).0, Halo2::from(x.c1).0))
+    }
+}
+
+impl From<Halo2<AffineG1>> for bn256::G1Affine {
+    fn from(p: Halo2<AffineG1>) -> Self {
+        bn256::G1Affine::from_xy(Halo2(p.0.x()).into(), Halo2(p.0.y()).into()).unwrap()
+    }
+}
+
+// halo2curves writes the identity as (0, 0), which `AffineG1` can't hold
+impl TryFrom<bn256::G1Affine> for Halo2<AffineG1> {
+    type Error = DeserializationError;
+
+    fn try_from(p: bn256::G1Affine) -> Result<Self, Self::Error> {
+        if bool::from(p.is_identity()) {
+            return Err(DeserializationError::UnexpectedInfinity);
+        }
+        Ok(Halo2(AffineG1::new(Halo2::from(p.x).0, Halo2::from(p.y).0)?))
+    }
+}
+
+impl From<Halo2<AffineG2>> for bn256::G2Affine {
+    fn from(p: Halo2<AffineG2>) -> Self {
+        bn256::G2Affine::from_xy(Halo2(p.0.x()).into(), Halo2(p.0.y()).into()).unwrap()
+    }
+}
+
+impl TryFrom<bn256::G2Affine> for Halo2<AffineG2> {
+    type Error = DeserializationError;
+
+    fn try_from(p: bn256::G2Affine) -> Result<Self, Self::Error> {
+        if bool::from(p.is_identity()) {
+            return Err(DeserializationError::UnexpectedInfinity);
+        }
+        Ok(Halo2(AffineG2::new(Halo2::from(p.x).0, Halo2::from(p.y).0)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use halo2curves::group::{Curve, GroupEncoding};
+    use rand::thread_rng;
+    use substrate_bn::{Group, G1, G2};
+
+    use crate::HashToCurve;
+
+    const MSGS: [&[u8]; 4] = [b"", b"abc", b"abcdef0123456789", &[b'q'; 128]];
+
+    #[test]
+    fn test_field_round_trip() {
+        let mut rng = thread_rng();
+        for _ in 0..8 {
+            let x = Fq::random(&mut rng);
+            assert!(Halo2::<Fq>::from(bn256::Fq::from(Halo2(x))).0 == x);
+            let x = Fr::random(&mut rng);
+            assert!(Halo2::<Fr>::from(bn256::Fr::from(Halo2(x))).0 == x);
+            let x = Fq2::new(Fq::random(&mut rng), Fq::random(&mut rng));
+            assert!(Halo2::<Fq2>::from(bn256::Fq2::from(Halo2(x))).0 == x);
+        }
+
+        // A byte-order slip would still round-trip, but not preserve small values
+        assert!(bn256::Fq::from(Halo2(Fq::from_str("3").unwrap())) == bn256::Fq::from(3u64));
+        assert!(bn256::Fr::from(Halo2(Fr::from_str("3").unwrap())) == bn256::Fr::from(3u64));
+    }
+
+    #[test]
+    fn test_hashed_points_round_trip() {
+        for msg in MSGS {
+            let q = AffineG1::hash(msg, AffineG1::DST);
+            let p = bn256::G1Affine::from(Halo2(q));
+            assert!(bool::from(p.is_on_curve()));
+            // And through halo2curves' own compressed encoding
+            let p = bn256::G1Affine::from_bytes(&p.to_bytes()).unwrap();
+            assert!(Halo2::<AffineG1>::try_from(p).unwrap().0 == q);
+
+            let q = AffineG2::hash(msg, AffineG2::DST);
+            let p = bn256::G2Affine::from(Halo2(q));
+            assert!(bool::from(p.is_on_curve()));
+            let p = bn256::G2Affine::from_bytes(&p.to_bytes()).unwrap();
+            assert!(Halo2::<AffineG2>::try_from(p).unwrap().0 == q);
+        }
+    }
+
+    #[test]
+    fn test_scalar_mul_agrees() {
+        assert!(bn256::G1Affine::from(Halo2(AffineG1::from(G1::one()))) == bn256::G1Affine::generator());
+        assert!(bn256::G2Affine::from(Halo2(AffineG2::from(G2::one()))) == bn256::G2Affine::generator());
+
+        let mut rng = thread_rng();
+        for _ in 0..4 {
+            let (a, b) = (Fr::random(&mut rng), Fr::random(&mut rng));
+            let (a_h, b_h) = (bn256::Fr::from(Halo2(a)), bn256::Fr::from(Halo2(b)));
+
+            let p = (bn256::G1Affine::generator() * a_h).to_affine();
+            let q = Halo2::<AffineG1>::try_from(p).unwrap().0;
+            assert!(bn256::G1Affine::from(Halo2(AffineG1::from(G1::from(q) * b))) == (p * b_h).to_affine());
+
+            let p = (bn256::G2Affine::generator() * a_h).to_affine();
+            let q = Halo2::<AffineG2>::try_from(p).unwrap().0;
+            assert!(bn256::G2Affine::from(Halo2(AffineG2::from(G2::from(q) * b))) == (p * b_h).to_affine());
+        }
+    }
+
+    #[test]
+    fn test_rejects_identity() {
+        assert_eq!(Halo2::<AffineG1>::try_from(bn256::G1Affine::identity()).err(), Some(DeserializationError::UnexpectedInfinity));
+        assert_eq!(Halo2::<AffineG2>::try_from(bn256::G2Affine::identity()).err(), Some(DeserializationError::UnexpectedInfinity));
+    }
+}
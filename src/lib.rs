@@ -1,64 +1,303 @@
-use substrate_bn::{AffineG1, Fr, GroupError};
-use rand::{thread_rng, Rng};
+#![cfg_attr(not(feature = "std"), no_std)]
 
+extern crate alloc;
+
+mod arith;
+#[cfg(feature = "ark")]
+pub mod ark;
+pub mod bls;
+pub mod display;
+pub mod error;
+pub mod expand;
+pub mod fr;
+#[forbid(unsafe_code)]
 pub mod g1;
+#[forbid(unsafe_code)]
 pub mod g2;
+#[cfg(feature = "halo2")]
+pub mod halo2;
+pub mod msm;
+#[cfg(feature = "std")]
+pub mod pedersen;
+pub mod point;
+#[cfg(feature = "std")]
+pub mod poly;
+pub mod schnorr;
+#[cfg(feature = "serde")]
+pub mod serde;
+pub mod serialize;
+#[cfg(feature = "std")]
+pub mod sigma;
+pub mod transcript;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+#[cfg(feature = "ark")]
+pub use ark::Ark;
+pub use bls::{
+    sign, verify, verify_aggregate, verify_batch, verify_multi_message, BlsPrivateKey, BlsPublicKey, BlsSignature,
+};
+pub use display::{DisplayHex, FromHex};
+pub use error::HashToCurveError;
+pub use expand::{
+    expand_message_xmd, expand_message_xmd_sha256, expand_message_xmd_sha384, expand_message_xmd_sha512,
+    expand_message_xmd_with, expand_message_xof, expand_message_xof_shake128, expand_message_xof_shake256,
+    expand_message_xof_with, ExpandError, XofVariant,
+};
+pub use fr::{hash_to_fr, hash_to_fr_many};
+#[cfg(feature = "halo2")]
+pub use halo2::Halo2;
+pub use g1::{batch_map_to_curve_g1, hash_to_field_sha512, hash_to_field_xof, LEN_PER_ELM};
+pub use msm::{msm, msm_g1, msm_g1_with_window, msm_g2, msm_g2_with_window, MsmError};
+#[cfg(feature = "std")]
+pub use pedersen::{
+    aggregate, blinding_generator, commit, commit_bytes, commit_deterministic, commit_g2, commit_sparse,
+    commit_with_table, derive_blinding, open, pedersen_hash, rerandomize, update_at, verify_commit, CommitBuilder,
+    Commitment, CommitmentError, CommitmentKey, CommitmentKeyG2, CommitmentParams, GeneratorTable, KeyedCommitment,
+    Opening,
+};
+pub use point::{PointValidation, SubgroupCheck};
+#[cfg(feature = "std")]
+pub use poly::{commit_eval_combination, commit_poly};
+pub use schnorr::{SchnorrPrivateKey, SchnorrPublicKey, SchnorrSignature};
+pub use serialize::{
+    CompressedG1, CompressedG2, DeserializationError, G1Encoding, G2Encoding, LeBytes, UncompressedG1, UncompressedG2,
+};
+#[cfg(feature = "std")]
+pub use sigma::{prove_equality, prove_opening, verify_equality, verify_opening, EqualityProof, OpeningProof};
+pub use transcript::Transcript;
+
+use alloc::vec::Vec;
 
 pub trait HashToCurve: Sized {
-    type FieldElement;
+    type FieldElement: HashToField;
+    /// DST of the RFC 9380 random-oracle suite for this group, used by `hash_default`
+    const DST: &'static [u8];
     fn sgn0(x: Self::FieldElement) -> u64;
-    fn map_to_curve(u: Self::FieldElement) -> Result<Self, GroupError>;
-    fn hash(msg: &[u8], dst: &[u8]) -> Self;
+    fn map_to_curve(u: Self::FieldElement) -> Result<Self, HashToCurveError>;
+    /// Maps a curve point into the prime-order subgroup; also usable on points from outside the crate
+    fn clear_cofactor(p: Self) -> Self;
+    fn try_hash(msg: &[u8], dst: &[u8]) -> Result<Self, HashToCurveError>;
+
+    fn hash(msg: &[u8], dst: &[u8]) -> Self {
+        Self::try_hash(msg, dst).expect("Failed to hash to curve")
+    }
+
+    fn hash_default(msg: &[u8]) -> Self {
+        Self::hash(msg, Self::DST)
+    }
+
+    /// Nonuniform encoding (`encode_to_curve`, the `_NU_` suites): a single `map_to_curve` of one
+    /// field element. Cheaper than `hash`, but the output only covers a fraction of the curve and is
+    /// distinguishable from random, so it must not stand in for a random oracle. It is enough where the
+    /// point only needs an unknown discrete log, such as a VRF or a commitment generator.
+    fn try_encode(msg: &[u8], dst: &[u8]) -> Result<Self, HashToCurveError> {
+        let mut u = Self::FieldElement::hash_to_field(msg, dst, 1)?;
+        let q = Self::map_to_curve(u.remove(0))?;
+        Ok(Self::clear_cofactor(q))
+    }
+
+    fn encode(msg: &[u8], dst: &[u8]) -> Self {
+        Self::try_encode(msg, dst).expect("Failed to encode to curve")
+    }
+
+    /// `try_hash` of every message under one DST, with `points[i]` equal to `hash(msgs[i], dst)`.
+    /// Messages are anything byte-like, e.g. `&[&[u8]]`, `Vec<Vec<u8>>` or `Vec<String>`. The default
+    /// hashes one message at a time; G1 and G2 instead expand every message first, map all 2n field
+    /// elements with a single shared inversion, then add and clear cofactors in input order. That
+    /// saves roughly one exponentiation per element but still pays the three square roots each. For
+    /// work spread across threads, use `hash_batch_parallel` or the free `hash_batch`
+    fn try_hash_batch<M: AsRef<[u8]>>(msgs: &[M], dst: &[u8]) -> Result<Vec<Self>, HashToCurveError> {
+        msgs.iter().map(|msg| Self::try_hash(msg.as_ref(), dst)).collect()
+    }
+
+    fn hash_batch<M: AsRef<[u8]>>(msgs: &[M], dst: &[u8]) -> Vec<Self> {
+        Self::try_hash_batch(msgs, dst).expect("Failed to hash to curve")
+    }
+
+    /// `hash` of every message, one rayon task per message. The hashes are independent, so
+    /// throughput scales close to linearly with cores once the batch outgrows the scheduling overhead
+    #[cfg(feature = "parallel")]
+    fn hash_batch_parallel<M: AsRef<[u8]> + Sync>(msgs: &[M], dst: &[u8]) -> Vec<Self>
+    where
+        Self: Send,
+    {
+        use rayon::prelude::*;
+        msgs.par_iter().map(|msg| Self::hash(msg.as_ref(), dst)).collect()
+    }
+}
+
+pub trait HashToField: Sized {
+    fn hash_to_field(msg: &[u8], dst: &[u8], count: usize) -> Result<Vec<Self>, HashToCurveError>;
+}
+
+/// Hashes every message under the same DST, spread across threads with the `parallel` feature
+pub fn hash_batch<C: HashToCurve + Send, M: AsRef<[u8]> + Sync>(msgs: &[M], dst: &[u8]) -> Result<Vec<C>, HashToCurveError> {
+    #[cfg(feature = "parallel")]
+    {
+        use rayon::prelude::*;
+        msgs.par_iter().map(|msg| C::try_hash(msg.as_ref(), dst)).collect()
+    }
+    #[cfg(not(feature = "parallel"))]
+    {
+        msgs.iter().map(|msg| C::try_hash(msg.as_ref(), dst)).collect()
+    }
 }
 
-// Pedersen-style vector commitment
-pub fn commit(vs: &[Fr], G: AffineG1, r: Fr) -> AffineG1 {
-    let dst = b"QUUX-V01-CS02-with-BN254G1_XMD:SHA-256_SVDW_RO_";
-    vs.iter().enumerate().fold(G * r, |acc, (i, &v)| {
-        acc + AffineG1::hash(&i.to_le_bytes(), dst) * v
-    })
+// Stores through a black-boxed reference so the write is not optimised away. `Fr` is a foreign type
+// and can't implement `Zeroize`, so the types holding secret scalars wipe them with this
+#[cfg(feature = "zeroize")]
+pub(crate) fn wipe<T: Copy>(x: &mut T, zero: T) {
+    *x = zero;
+    core::hint::black_box(x);
 }
 
+// https://www.rfc-editor.org/rfc/rfc9380.html#name-hash_to_field-implementatio
+pub fn hash_to_field<F: HashToField>(msg: &[u8], dst: &[u8], count: usize) -> Result<Vec<F>, HashToCurveError> {
+    F::hash_to_field(msg, dst, count)
+}
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use substrate_bn::{AffineG1, AffineG2, Fq, Fq2};
+
+    fn hash_via_trait<C: HashToCurve>(msg: &[u8], dst: &[u8]) -> C {
+        C::hash(msg, dst)
+    }
+
+    fn sgn0_via_trait<C: HashToCurve>(x: C::FieldElement) -> u64 {
+        C::sgn0(x)
+    }
 
     #[test]
-    fn test_commit_additive_homomorphic() {
-        let mut rng = thread_rng();
+    fn test_hash_to_curve_trait_g1() {
+        let q: AffineG1 = hash_via_trait(b"abc", b"QUUX-V01-CS02-with-BN254G1_XMD:SHA-256_SVDW_RO_");
+        assert!(q == AffineG1::new(Fq::from_str("16267524812466668166267883771992486438338357688076900798565538061554532963281").unwrap(), Fq::from_str("1844916233815282837483764409618609279507070495361570126601873459268232811805").unwrap()).unwrap());
+        assert_eq!(sgn0_via_trait::<AffineG1>(Fq::one()), 1);
+    }
 
-        let v1 = (0..10).map(|_| Fr::random(&mut rng)).collect::<Vec<_>>();
-        let v2 = (0..10).map(|_| Fr::random(&mut rng)).collect::<Vec<_>>();
-        let v_sum: Vec<Fr> = v1.iter().zip(&v2).map(|(&a, &b)| a + b).collect();
+    #[test]
+    fn test_hash_to_curve_trait_g2() {
+        let _: fn(&[u8], &[u8]) -> AffineG2 = hash_via_trait::<AffineG2>;
+        assert_eq!(sgn0_via_trait::<AffineG2>(Fq2::one()), 1);
+        assert_eq!(sgn0_via_trait::<AffineG2>(Fq2::new(Fq::zero(), Fq::one())), 1);
+        assert_eq!(sgn0_via_trait::<AffineG2>(Fq2::zero()), 0);
+    }
+
+    #[test]
+    fn test_hash_to_field_trait() {
+        let u = <Fq as HashToField>::hash_to_field(b"abc", b"QUUX-V01-CS02-with-BN254G1_XMD:SHA-256_SVDW_RO_", 2).unwrap();
+        assert!(u[0] == Fq::from_str("7951370986911800256774597109927097176311261202951929331835478768207980370345").unwrap());
+    }
+
+    #[test]
+    fn test_hash_to_field_counts() {
+        let dst = b"QUUX-V01-CS02-with-BN254G1_XMD:SHA-256_SVDW_RO_";
+
+        // len_in_bytes is bound into the expansion, so each count yields an unrelated sequence
+        let u: Vec<Fq> = hash_to_field(b"abc", dst, 1).unwrap();
+        assert!(u == vec![Fq::from_str("16263588484820813845910825919107725801476647590776828225685395515459090177907").unwrap()]);
+
+        let u: Vec<Fq> = hash_to_field(b"abc", dst, 3).unwrap();
+        let expected = [
+            "9306365675222469555057953804662186580927297125557215099343870995285971024790",
+            "14077801638106042819554788129463865710982336757948300507182700590055600544348",
+            "13166056078436423190808544678769256069159635247054255458277881281281956815692",
+        ];
+        assert_eq!(u.len(), expected.len());
+        assert!(u.iter().zip(expected).all(|(&u, e)| u == Fq::from_str(e).unwrap()));
+
+        let u: Vec<Fq> = hash_to_field(b"abc", dst, 8).unwrap();
+        let expected = [
+            "6056221622026723257452699948046281763107619564390149986274367252132781866697",
+            "3564706924276502939820367316748359858292721741485016470743024554160641936662",
+            "2581156069958102130767831477858762904780730528279664994877925253285352896950",
+            "20033905046226158475707727070586760651350920714281283701646391921292366907963",
+            "3120417565055534408211773909924554542512341390854925470927150606665679749131",
+            "16013989190641767910320964356555845340139477385499639949424285709885979202444",
+            "12456689608648406530817659447196362314959679564014502765119751613306951448105",
+            "15026441188129393084354575040197032747042997678120966330075944400329804557301",
+        ];
+        assert_eq!(u.len(), expected.len());
+        assert!(u.iter().zip(expected).all(|(&u, e)| u == Fq::from_str(e).unwrap()));
+    }
+
+    #[test]
+    fn test_try_hash_rejects_bad_dst() {
+        assert!(matches!(AffineG1::try_hash(b"abc", b""), Err(HashToCurveError::InvalidDst)));
+        assert!(matches!(AffineG2::try_hash(b"abc", b""), Err(HashToCurveError::InvalidDst)));
+        assert!(matches!(Fq::hash_to_field(b"abc", b"", 2), Err(HashToCurveError::InvalidDst)));
+    }
 
-        let r1 = Fr::random(&mut rng);
-        let r2 = Fr::random(&mut rng);
-        let r_sum = r1 + r2;
+    #[test]
+    fn test_hash_oversize_dst() {
+        let dst = [b'A'; 300];
+        let u: Vec<Fq> = hash_to_field(b"abc", &dst, 2).unwrap();
+        assert!(u[0] == Fq::from_str("4417897949362603935227489319866014069024921608735917730170240234975320986697").unwrap());
+        assert!(u[1] == Fq::from_str("9832537471051248091782227839086693247353781491898790467454181793499374508860").unwrap());
+
+        let q = AffineG1::hash(b"abc", &dst);
+        assert!(q == AffineG1::new(Fq::from_str("17677125637015735208734684642380423127947393970462027253646853790348282781715").unwrap(), Fq::from_str("1787123336746071541487235847711307621692123633056952707042379753900686843199").unwrap()).unwrap());
+    }
 
-        let G = AffineG1::default();
+    #[test]
+    fn test_hash_batch() {
+        let msgs: [&[u8]; 4] = [b"", b"abc", b"abcdef0123456789", &[b'q'; 128]];
+        let g1 = b"QUUX-V01-CS02-with-BN254G1_XMD:SHA-256_SVDW_RO_";
+        let g2 = b"QUUX-V01-CS02-with-BN254G2_XMD:SHA-256_SVDW_RO_";
 
-        let c1 = commit(&v1, G, r1);
-        let c2 = commit(&v2, G, r2);
-        let c_sum = commit(&v_sum, G, r_sum);
+        // Same points whether or not the batch was split across threads
+        let q: Vec<AffineG1> = hash_batch(&msgs, g1).unwrap();
+        assert!(q.iter().zip(msgs).all(|(&q, msg)| q == AffineG1::hash(msg, g1)));
+        let q: Vec<AffineG2> = hash_batch(&msgs, g2).unwrap();
+        assert!(q.iter().zip(msgs).all(|(&q, msg)| q == AffineG2::hash(msg, g2)));
 
-        assert_eq!(c_sum, c1 + c2);
+        assert!(matches!(hash_batch::<AffineG1, _>(&msgs, b""), Err(HashToCurveError::InvalidDst)));
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_hash_batch_parallel() {
+        let msgs: Vec<Vec<u8>> = (0..64u32).map(|i| i.to_le_bytes().to_vec()).collect();
+        let msgs: Vec<&[u8]> = msgs.iter().map(|m| m.as_slice()).collect();
+        let q = AffineG1::hash_batch_parallel(&msgs, AffineG1::DST);
+        assert!(q.iter().zip(&msgs).all(|(&q, msg)| q == AffineG1::hash(msg, AffineG1::DST)));
+        assert!(AffineG2::hash_batch_parallel::<&[u8]>(&[], AffineG2::DST).is_empty());
     }
 
     #[test]
-    fn test_commit_scalar_multiplication_homomorphic() {
-        let mut rng = thread_rng();
+    fn test_trait_hash_batch() {
+        let msgs: [&[u8]; 3] = [b"", b"abc", b"abcdef0123456789"];
+        let q = AffineG2::hash_batch(&msgs, AffineG2::DST);
+        assert!(q.iter().zip(msgs).all(|(&q, msg)| q == AffineG2::hash(msg, AffineG2::DST)));
+        assert!(AffineG2::hash_batch::<&[u8]>(&[], AffineG2::DST).is_empty());
+    }
 
-        let v = (0..10).map(|_| Fr::random(&mut rng)).collect::<Vec<_>>();
-        let scalar = Fr::random(&mut rng);
-        let v_scaled: Vec<Fr> = v.iter().map(|&x| x * scalar).collect();
+    #[test]
+    fn test_hash_batch_owned_messages() {
+        // Owned and string messages hash exactly like the equivalent byte slices, in input order
+        let owned: Vec<Vec<u8>> = vec![b"abc".to_vec(), Vec::new(), b"abc".to_vec()];
+        let strings: Vec<String> = vec!["abc".into(), String::new(), "abc".into()];
+        let q = AffineG1::hash_batch(&owned, AffineG1::DST);
+        assert!(q == AffineG1::hash_batch(&strings, AffineG1::DST));
+        assert!(q[0] == AffineG1::hash(b"abc", AffineG1::DST) && q[1] == AffineG1::hash(b"", AffineG1::DST) && q[0] == q[2]);
 
-        let r = Fr::random(&mut rng);
-        let G = AffineG1::default();
+        let q: Vec<AffineG2> = hash_batch(&strings, AffineG2::DST).unwrap();
+        assert!(q == AffineG2::hash_batch(&owned, AffineG2::DST));
+    }
 
-        let c = commit(&v, G, r);
-        let c_scaled = commit(&v_scaled, G, r * scalar);
+    #[test]
+    fn test_hash_default() {
+        assert_eq!(AffineG1::DST, b"QUUX-V01-CS02-with-BN254G1_XMD:SHA-256_SVDW_RO_");
+        assert_eq!(AffineG2::DST, b"QUUX-V01-CS02-with-BN254G2_XMD:SHA-256_SVDW_RO_");
+        assert!(AffineG1::hash_default(b"abc") == hash_via_trait::<AffineG1>(b"abc", AffineG1::DST));
+        assert!(AffineG2::hash_default(b"abc") == AffineG2::hash(b"abc", AffineG2::DST));
+    }
 
-        assert_eq!(c_scaled, c * scalar);
+    #[test]
+    fn test_try_hash_matches_hash() {
+        let dst = b"QUUX-V01-CS02-with-BN254G1_XMD:SHA-256_SVDW_RO_";
+        assert!(AffineG1::try_hash(b"abc", dst).unwrap() == AffineG1::hash(b"abc", dst));
     }
-}
\ No newline at end of file
+}
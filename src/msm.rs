@@ -0,0 +1,193 @@
+use alloc::{vec, vec::Vec};
+
+use substrate_bn::{arith::U256, AffineG1, AffineG2, Fr, G1, G2};
+
+use crate::arith::Arith;
+
+/// Window size for Pippenger's bucket method, roughly ln(n) + 2 as in Henry (2010), capped at the
+/// 16 bits `pippenger` accepts
+pub fn window_size(n: usize) -> usize {
+    if n < 32 {
+        3
+    } else {
+        // ceil(ln n) by repeated multiplication, since `f64::ln` needs std
+        let mut k = 0;
+        let mut e = 1.0;
+        while e < n as f64 {
+            e *= core::f64::consts::E;
+            k += 1;
+        }
+        (k + 2).min(16)
+    }
+}
+
+// Bits [offset, offset + c) of the canonical scalar
+fn window(s: &U256, offset: usize, c: usize) -> usize {
+    (offset..(offset + c).min(256))
+        .rev()
+        .fold(0, |acc, bit| (acc << 1) | s.get_bit(bit).unwrap() as usize)
+}
+
+// Sum of bucket_i * i for the c-bit window starting at `offset`
+fn window_sum<G: Arith>(points: &[G], scalars: &[U256], offset: usize, c: usize) -> G {
+    let mut buckets = vec![G::zero(); (1 << c) - 1];
+    for (p, s) in points.iter().zip(scalars) {
+        let idx = window(s, offset, c);
+        if idx != 0 {
+            buckets[idx - 1] = buckets[idx - 1].add_point(*p);
+        }
+    }
+
+    // sum_i i * bucket_i via running sums from the top bucket down
+    let mut running = G::zero();
+    let mut sum = G::zero();
+    for b in buckets.into_iter().rev() {
+        running = running.add_point(b);
+        sum = sum.add_point(running);
+    }
+    sum
+}
+
+// Pippenger's bucket method over any substrate_bn group in projective coordinates, with c-bit windows.
+// Additions go through the arith backend, so inside the zkVM the G1 ones use the precompile
+pub(crate) fn pippenger<G: Arith>(points: &[G], scalars: &[Fr], c: usize) -> G {
+    assert_eq!(points.len(), scalars.len(), "msm needs one scalar per point");
+    assert!((1..=16).contains(&c), "window size must be between 1 and 16 bits");
+
+    let scalars: Vec<U256> = scalars.iter().map(|s| s.into_u256()).collect();
+    let windows = (256 + c - 1) / c;
+
+    #[cfg(feature = "parallel")]
+    let sums: Vec<G> = {
+        use rayon::prelude::*;
+        (0..windows).into_par_iter().map(|w| window_sum(points, &scalars, w * c, c)).collect()
+    };
+    #[cfg(not(feature = "parallel"))]
+    let sums: Vec<G> = (0..windows).map(|w| window_sum(points, &scalars, w * c, c)).collect();
+
+    sums.into_iter().rev().fold(G::zero(), |mut acc, sum| {
+        for _ in 0..c {
+            acc = acc.add_point(acc);
+        }
+        acc.add_point(sum)
+    })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MsmError {
+    /// `points` and `scalars` have different lengths
+    LengthMismatch { points: usize, scalars: usize },
+    /// The window is outside the 1 to 16 bits `pippenger` supports
+    InvalidWindow(usize),
+}
+
+fn check(points: usize, scalars: usize, c: usize) -> Result<(), MsmError> {
+    if points != scalars {
+        return Err(MsmError::LengthMismatch { points, scalars });
+    }
+    if !(1..=16).contains(&c) {
+        return Err(MsmError::InvalidWindow(c));
+    }
+    Ok(())
+}
+
+/// Multi-scalar multiplication in G1, the group commitments live in. Panics if `points` and
+/// `scalars` differ in length
+pub fn msm(points: &[AffineG1], scalars: &[Fr]) -> AffineG1 {
+    msm_g1(points, scalars)
+}
+
+/// Panics if `points` and `scalars` differ in length
+pub fn msm_g1(points: &[AffineG1], scalars: &[Fr]) -> AffineG1 {
+    let points: Vec<G1> = points.iter().map(|&p| p.into()).collect();
+    pippenger(&points, scalars, window_size(points.len())).into()
+}
+
+/// `msm_g1` with an explicit window of 1 to 16 bits
+pub fn msm_g1_with_window(points: &[AffineG1], scalars: &[Fr], c: usize) -> Result<AffineG1, MsmError> {
+    check(points.len(), scalars.len(), c)?;
+    let points: Vec<G1> = points.iter().map(|&p| p.into()).collect();
+    Ok(pippenger(&points, scalars, c).into())
+}
+
+/// Panics if `points` and `scalars` differ in length
+pub fn msm_g2(points: &[AffineG2], scalars: &[Fr]) -> AffineG2 {
+    let points: Vec<G2> = points.iter().map(|&p| p.into()).collect();
+    pippenger(&points, scalars, window_size(points.len())).into()
+}
+
+/// `msm_g2` with an explicit window of 1 to 16 bits
+pub fn msm_g2_with_window(points: &[AffineG2], scalars: &[Fr], c: usize) -> Result<AffineG2, MsmError> {
+    check(points.len(), scalars.len(), c)?;
+    let points: Vec<G2> = points.iter().map(|&p| p.into()).collect();
+    Ok(pippenger(&points, scalars, c).into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::thread_rng;
+    use substrate_bn::Group;
+
+    fn naive(points: &[AffineG1], scalars: &[Fr]) -> G1 {
+        points.iter().zip(scalars).fold(G1::zero(), |acc, (&p, &s)| acc + G1::from(p) * s)
+    }
+
+    #[test]
+    fn test_msm_g1_matches_naive() {
+        let mut rng = thread_rng();
+        for n in [1, 2, 7, 31, 33, 100, 257, 1000] {
+            let points: Vec<AffineG1> = (0..n).map(|_| (G1::one() * Fr::random(&mut rng)).into()).collect();
+            let scalars: Vec<Fr> = (0..n).map(|_| Fr::random(&mut rng)).collect();
+            assert!(G1::from(msm(&points, &scalars)) == naive(&points, &scalars));
+        }
+    }
+
+    #[test]
+    fn test_msm_g1_edge_scalars() {
+        let points: Vec<AffineG1> = (1..=4).map(|i| (G1::one() * Fr::from_str(&i.to_string()).unwrap()).into()).collect();
+        let scalars = [Fr::zero(), Fr::one(), -Fr::one(), Fr::from_str("2").unwrap()];
+        assert!(G1::from(msm_g1(&points, &scalars)) == naive(&points, &scalars));
+    }
+
+    #[test]
+    fn test_msm_g2_two_points() {
+        let mut rng = thread_rng();
+        for _ in 0..4 {
+            let p: AffineG2 = (G2::one() * Fr::random(&mut rng)).into();
+            let q: AffineG2 = (G2::one() * Fr::random(&mut rng)).into();
+            let (a, b) = (Fr::random(&mut rng), Fr::random(&mut rng));
+            assert!(G2::from(msm_g2(&[p, q], &[a, b])) == G2::from(p) * a + G2::from(q) * b);
+        }
+    }
+
+    #[test]
+    fn test_window_size() {
+        assert_eq!(window_size(1), 3);
+        assert_eq!(window_size(1000), 9);
+        assert_eq!(window_size(1 << 21), 16);
+        assert_eq!(window_size(usize::MAX), 16);
+    }
+
+    #[test]
+    fn test_msm_window_sizes_agree() {
+        let mut rng = thread_rng();
+        let points: Vec<AffineG2> = (0..20).map(|_| (G2::one() * Fr::random(&mut rng)).into()).collect();
+        let scalars: Vec<Fr> = (0..20).map(|_| Fr::random(&mut rng)).collect();
+        let expected = G2::from(msm_g2(&points, &scalars));
+        for c in [1, 4, 7, 10] {
+            assert!(G2::from(msm_g2_with_window(&points, &scalars, c).unwrap()) == expected);
+        }
+    }
+
+    #[test]
+    fn test_msm_with_window_rejects_bad_input() {
+        let points = [AffineG1::one(); 3];
+        let scalars = [Fr::one(); 3];
+        assert_eq!(msm_g1_with_window(&points, &scalars[..2], 4), Err(MsmError::LengthMismatch { points: 3, scalars: 2 }));
+        assert_eq!(msm_g1_with_window(&points, &scalars, 0), Err(MsmError::InvalidWindow(0)));
+        assert_eq!(msm_g1_with_window(&points, &scalars, 17), Err(MsmError::InvalidWindow(17)));
+        assert!(msm_g1_with_window(&points, &scalars, 16).unwrap() == msm_g1(&points, &scalars));
+        assert_eq!(msm_g2_with_window(&[AffineG2::one()], &[], 4).err(), Some(MsmError::LengthMismatch { points: 1, scalars: 0 }));
+    }
+}
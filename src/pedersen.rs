@@ -0,0 +1,1273 @@
+use std::collections::HashMap;
+use std::ops::Range;
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+use once_cell::sync::Lazy;
+use rand_core::RngCore;
+use substrate_bn::{AffineG1, AffineG2, Fr, Group, G1};
+use subtle::ConstantTimeEq;
+
+use crate::fr::hash_to_fr_indexed;
+use crate::msm::{pippenger, window_size};
+use crate::point::PointValidation;
+use crate::serialize::fr_to_bytes;
+use crate::{expand_message_xmd, msm, msm_g2, G1Encoding, HashToCurve, HashToCurveError, HashToField};
+
+// Hash inputs start with a tag byte, so the blinding generator H and the value generators H_i come
+// from disjoint input spaces and can never coincide, whatever the index encoding
+const BLINDING_TAG: u8 = 0x00;
+const VALUE_TAG: u8 = 0x01;
+const HASH_TAG: u8 = 0x02;
+
+// Tag byte then the index as a little-endian u64, so 32-bit guests and 64-bit hosts derive the
+// same generators
+#[cfg(not(feature = "legacy-generators"))]
+fn generator_input(i: usize) -> Vec<u8> {
+    let mut input = vec![VALUE_TAG];
+    input.extend((i as u64).to_le_bytes());
+    input
+}
+
+// Original derivation from the native-width `usize`. Only reproduces old commitments on the
+// pointer width they were made on
+#[cfg(feature = "legacy-generators")]
+fn generator_input(i: usize) -> Vec<u8> {
+    i.to_le_bytes().to_vec()
+}
+
+pub(crate) fn value_generator(i: usize, dst: &[u8]) -> AffineG1 {
+    AffineG1::hash(&generator_input(i), dst)
+}
+
+#[cfg(feature = "parallel")]
+fn hash_value_generators(indices: Range<usize>, dst: &[u8]) -> Vec<AffineG1> {
+    use rayon::prelude::*;
+    indices.into_par_iter().map(|i| value_generator(i, dst)).collect()
+}
+
+#[cfg(not(feature = "parallel"))]
+fn hash_value_generators(indices: Range<usize>, dst: &[u8]) -> Vec<AffineG1> {
+    indices.map(|i| value_generator(i, dst)).collect()
+}
+
+/// Blinding generator H for `dst`, independent of every value generator. Panics if `dst` is empty
+#[cfg(not(feature = "legacy-generators"))]
+pub fn blinding_generator(dst: &[u8]) -> AffineG1 {
+    AffineG1::hash(&[&[BLINDING_TAG], &b"blinding"[..]].concat(), dst)
+}
+
+#[cfg(feature = "legacy-generators")]
+pub fn blinding_generator(dst: &[u8]) -> AffineG1 {
+    AffineG1::hash(&[0u8], dst)
+}
+
+pub struct CommitmentParams {
+    /// Blinding generator, hashed from the DST so nobody knows its discrete log
+    pub generator: AffineG1,
+    /// Domain separation tag the value generators are hashed under
+    pub dst: Vec<u8>,
+}
+
+impl CommitmentParams {
+    pub fn new(dst: &[u8]) -> Result<Self, HashToCurveError> {
+        if dst.is_empty() {
+            return Err(HashToCurveError::InvalidDst);
+        }
+
+        Ok(CommitmentParams {
+            generator: blinding_generator(dst),
+            dst: dst.to_vec(),
+        })
+    }
+
+    /// Explicit blinding base. Only sound if nobody knows its discrete log relative to the hashed
+    /// value generators; prefer `new`
+    pub fn with_generator(dst: &[u8], generator: AffineG1) -> Result<Self, HashToCurveError> {
+        let mut params = Self::new(dst)?;
+        params.generator = generator;
+        Ok(params)
+    }
+}
+
+/// Blinding generator plus the first `n` value generators, hashed once up front
+pub struct GeneratorTable {
+    generator: AffineG1,
+    inner: Vec<AffineG1>,
+}
+
+impl GeneratorTable {
+    /// Panics if `dst` is empty, like `AffineG1::hash`
+    pub fn new(n: usize, dst: &[u8]) -> Self {
+        Self::with_generator(n, dst, blinding_generator(dst))
+    }
+
+    fn with_generator(n: usize, dst: &[u8], generator: AffineG1) -> Self {
+        GeneratorTable { generator, inner: hash_value_generators(0..n, dst) }
+    }
+
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+}
+
+pub fn commit_with_table(vs: &[Fr], table: &GeneratorTable, r: Fr) -> AffineG1 {
+    assert!(vs.len() <= table.len(), "GeneratorTable holds {} generators but {} values were given", table.len(), vs.len());
+    commit_with_generators(vs, table.generator, &table.inner, r)
+}
+
+fn commit_with_generators(vs: &[Fr], generator: AffineG1, generators: &[AffineG1], r: Fr) -> AffineG1 {
+    let points: Vec<AffineG1> = std::iter::once(generator).chain(generators[..vs.len()].iter().copied()).collect();
+    let scalars: Vec<Fr> = std::iter::once(r).chain(vs.iter().copied()).collect();
+    msm(&points, &scalars)
+}
+
+/// Generator table bound to its DST, so it can grow when a longer vector shows up
+pub struct CommitmentKey {
+    dst: Vec<u8>,
+    table: GeneratorTable,
+}
+
+impl CommitmentKey {
+    pub fn new(n: usize, dst: &[u8]) -> Result<Self, HashToCurveError> {
+        Ok(Self::from_params(n, CommitmentParams::new(dst)?))
+    }
+
+    /// Key over an explicit blinding base, see `CommitmentParams::with_generator`
+    pub fn with_blinding_generator(n: usize, dst: &[u8], generator: AffineG1) -> Result<Self, HashToCurveError> {
+        Ok(Self::from_params(n, CommitmentParams::with_generator(dst, generator)?))
+    }
+
+    fn from_params(n: usize, params: CommitmentParams) -> Self {
+        CommitmentKey {
+            table: GeneratorTable::with_generator(n, &params.dst, params.generator),
+            dst: params.dst,
+        }
+    }
+
+    /// Hashes any value generators missing below index `n`; already derived ones are kept. Panics
+    /// on a key from `from_points` that is too short, since it has no DST to hash under
+    pub fn ensure_len(&mut self, n: usize) {
+        assert!(n <= self.len() || !self.dst.is_empty(), "imported CommitmentKey holds only {} generators", self.len());
+        for i in self.table.len()..n {
+            self.table.inner.push(value_generator(i, &self.dst));
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.table.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.table.is_empty()
+    }
+
+    /// Blinding generator followed by the value generators, each uncompressed as big-endian x || y,
+    /// the layout the EVM bn256 precompiles read
+    pub fn export(&self) -> Vec<[u8; 64]> {
+        std::iter::once(&self.table.generator).chain(&self.table.inner).map(|p| p.to_uncompressed()).collect()
+    }
+
+    /// `export` as one hex string per line
+    pub fn export_hex(&self) -> String {
+        self.export().iter().map(|p| hex::encode(p) + "\n").collect()
+    }
+
+    /// `export` as a JSON array of 0x-prefixed hex strings
+    pub fn export_json(&self) -> String {
+        let points: Vec<String> = self.export().iter().map(|p| format!("\"0x{}\"", hex::encode(p))).collect();
+        format!("[{}]", points.join(","))
+    }
+
+    /// Key over externally supplied generators in `export` order, blinding generator first. Index 0
+    /// is reported when the vector is empty. The key has no DST, so it cannot grow past its length
+    pub fn from_points(points: Vec<AffineG1>) -> Result<Self, CommitmentError> {
+        if let Some(index) = points.iter().position(|p| !p.is_on_curve() || p.is_identity()) {
+            return Err(CommitmentError::InvalidGenerator(index));
+        }
+        let mut points = points.into_iter();
+        let generator = points.next().ok_or(CommitmentError::InvalidGenerator(0))?;
+        Ok(CommitmentKey { dst: Vec::new(), table: GeneratorTable { generator, inner: points.collect() } })
+    }
+
+    /// Panics if `vs` is longer than the key; call `ensure_len` first
+    pub fn commit(&self, vs: &[Fr], r: Fr) -> AffineG1 {
+        commit_with_table(vs, &self.table, r)
+    }
+
+    /// Short hash of the length, blinding generator and DST; keys with equal fingerprints commit
+    /// under the same generators. Keys from `from_points` have no DST, so their value generators are
+    /// hashed in its place
+    pub fn fingerprint(&self) -> [u8; FINGERPRINT_LEN] {
+        let len = (self.len() as u64).to_be_bytes();
+        let mut msg = [&len[..], &self.table.generator.to_uncompressed(), &self.dst].concat();
+        if self.dst.is_empty() {
+            self.table.inner.iter().for_each(|p| msg.extend(p.to_uncompressed()));
+        }
+        let bytes = expand_message_xmd(&msg, FINGERPRINT_DST, FINGERPRINT_LEN).expect("Failed to fingerprint key");
+        bytes.try_into().unwrap()
+    }
+
+    pub fn commit_keyed(&self, vs: &[Fr], r: Fr) -> KeyedCommitment {
+        KeyedCommitment { commitment: Commitment(self.commit(vs, r)), fingerprint: self.fingerprint() }
+    }
+}
+
+const BATCH_DST: &[u8] = b"PEDERSEN-BATCH-OPENING-V01";
+
+/// Checks many openings at once with weights hashed from every commitment and opening, one per
+/// index, so the result is deterministic for any batch size. A single bad opening fails the batch
+/// except with probability ~1/r
+pub fn verify_batch(key: &CommitmentKey, items: &[(AffineG1, Opening)]) -> bool {
+    match batch_weights(items) {
+        Ok(weights) => verify_batch_weighted(key, items, &weights),
+        Err(_) => false,
+    }
+}
+
+fn batch_weights(items: &[(AffineG1, Opening)]) -> Result<Vec<Fr>, HashToCurveError> {
+    let mut seed = Vec::new();
+    for (c, opening) in items {
+        seed.extend(c.to_uncompressed());
+        seed.extend((opening.values.len() as u64).to_be_bytes());
+        opening.values.iter().for_each(|&v| seed.extend(fr_to_bytes(v)));
+        seed.extend(fr_to_bytes(opening.blinding));
+    }
+    hash_to_fr_indexed(&seed, BATCH_DST, items.len())
+}
+
+/// `verify_batch` with weights drawn from the caller's RNG
+pub fn verify_batch_with_rng<R: RngCore>(key: &CommitmentKey, items: &[(AffineG1, Opening)], rng: &mut R) -> bool {
+    let weights: Vec<Fr> = items.iter().map(|_| Fr::random(rng)).collect();
+    verify_batch_weighted(key, items, &weights)
+}
+
+// Σ ρ_j·r_j·G + Σ_i (Σ_j ρ_j·v_ji)·H_i - Σ ρ_j·C_j == 0 as one MSM
+fn verify_batch_weighted(key: &CommitmentKey, items: &[(AffineG1, Opening)], weights: &[Fr]) -> bool {
+    let len = items.iter().map(|(_, o)| o.values.len()).max().unwrap_or(0);
+    if len > key.len() {
+        return false;
+    }
+
+    let mut blinding = Fr::zero();
+    let mut values = vec![Fr::zero(); len];
+    for ((_, opening), &w) in items.iter().zip(weights) {
+        blinding = blinding + w * opening.blinding;
+        for (acc, &v) in values.iter_mut().zip(&opening.values) {
+            *acc = *acc + w * v;
+        }
+    }
+
+    let points: Vec<G1> = std::iter::once(key.table.generator)
+        .chain(key.table.inner[..len].iter().copied())
+        .chain(items.iter().map(|(c, _)| *c))
+        .map(G1::from)
+        .collect();
+    let scalars: Vec<Fr> = std::iter::once(blinding)
+        .chain(values)
+        .chain(weights.iter().map(|&w| -w))
+        .collect();
+    pippenger(&points, &scalars, window_size(points.len())).is_zero()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommitmentError {
+    /// The position has no generator in the key
+    IndexOutOfRange { index: usize, len: usize },
+    /// A sparse vector lists the same position twice
+    DuplicateIndex(usize),
+    /// Keyed commitments were made under keys with a different DST or length
+    FingerprintMismatch,
+    /// An imported generator is the identity or off the curve
+    InvalidGenerator(usize),
+    /// `aggregate` was given no commitments
+    EmptyAggregate,
+}
+
+/// A commitment point, for code that combines commitments rather than computes them. Serializes
+/// compressed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Commitment(#[cfg_attr(feature = "serde", serde(with = "crate::serde::g1_compressed"))] pub AffineG1);
+
+/// Sum of the commitments, which opens to the element-wise sum of the values and of the blindings.
+/// Only meaningful for commitments under the same key. An empty slice has no commitment to return
+pub fn aggregate(commitments: &[Commitment]) -> Result<Commitment, CommitmentError> {
+    if commitments.is_empty() {
+        return Err(CommitmentError::EmptyAggregate);
+    }
+    Ok(Commitment(commitments.iter().fold(G1::zero(), |acc, c| acc + G1::from(c.0)).into()))
+}
+
+const FINGERPRINT_DST: &[u8] = b"PEDERSEN-KEY-FINGERPRINT-V01";
+pub const FINGERPRINT_LEN: usize = 8;
+
+/// Commitment tagged with the fingerprint of the key that produced it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyedCommitment {
+    pub commitment: Commitment,
+    pub fingerprint: [u8; FINGERPRINT_LEN],
+}
+
+impl KeyedCommitment {
+    /// Like `aggregate`, but refuses commitments from keys with different fingerprints
+    pub fn aggregate(commitments: &[KeyedCommitment]) -> Result<KeyedCommitment, CommitmentError> {
+        let fingerprint = commitments.first().ok_or(CommitmentError::EmptyAggregate)?.fingerprint;
+        if commitments.iter().any(|c| c.fingerprint != fingerprint) {
+            return Err(CommitmentError::FingerprintMismatch);
+        }
+        let points: Vec<Commitment> = commitments.iter().map(|c| c.commitment).collect();
+        Ok(KeyedCommitment { commitment: aggregate(&points)?, fingerprint })
+    }
+
+    /// Compressed point followed by the fingerprint
+    pub fn to_bytes(&self) -> [u8; 32 + FINGERPRINT_LEN] {
+        let mut out = [0u8; 32 + FINGERPRINT_LEN];
+        out[..32].copy_from_slice(&self.commitment.0.to_compressed());
+        out[32..].copy_from_slice(&self.fingerprint);
+        out
+    }
+}
+
+/// Adds `blinding_generator * delta`, so the same values now open with blinding `r + delta`
+pub fn rerandomize(c: AffineG1, delta: Fr, blinding_generator: AffineG1) -> AffineG1 {
+    (G1::from(c) + G1::from(blinding_generator) * delta).into()
+}
+
+/// Replaces `old_value` at `index` with `new_value` by adding `(new - old) * H_index`; the blinding is unchanged
+pub fn update_at(
+    key: &CommitmentKey,
+    c: AffineG1,
+    index: usize,
+    old_value: Fr,
+    new_value: Fr,
+) -> Result<AffineG1, CommitmentError> {
+    let h = *key.table.inner.get(index).ok_or(CommitmentError::IndexOutOfRange { index, len: key.len() })?;
+    if old_value == new_value {
+        return Ok(c);
+    }
+    Ok((G1::from(c) + G1::from(h) * (new_value - old_value)).into())
+}
+
+/// Commits to a sparse vector given as (index, value) pairs, touching only the listed generators.
+/// Equals the dense commitment with zeros everywhere else
+pub fn commit_sparse(key: &CommitmentKey, entries: &[(usize, Fr)], r: Fr) -> Result<AffineG1, CommitmentError> {
+    let mut seen = std::collections::HashSet::with_capacity(entries.len());
+    let mut points = Vec::with_capacity(entries.len() + 1);
+    let mut scalars = Vec::with_capacity(entries.len() + 1);
+    points.push(key.table.generator);
+    scalars.push(r);
+
+    for &(index, value) in entries {
+        if !seen.insert(index) {
+            return Err(CommitmentError::DuplicateIndex(index));
+        }
+        let h = *key.table.inner.get(index).ok_or(CommitmentError::IndexOutOfRange { index, len: key.len() })?;
+        points.push(h);
+        scalars.push(value);
+    }
+    Ok(msm(&points, &scalars))
+}
+
+// Bytes per chunk; 31 bytes is below 2^248 < r, so every chunk maps to a distinct Fr without reduction
+const CHUNK_LEN: usize = 31;
+
+// [len(msg), chunk_0, chunk_1, ...] with each chunk read big-endian and the last one zero-padded on the
+// right. The length prefix tells trailing zeros apart from padding, so the map is injective
+fn bytes_to_fr(msg: &[u8]) -> Vec<Fr> {
+    let len = Fr::from_str(&msg.len().to_string()).unwrap();
+    std::iter::once(len)
+        .chain(msg.chunks(CHUNK_LEN).map(|chunk| {
+            let mut bytes = [0u8; 32];
+            bytes[1..1 + chunk.len()].copy_from_slice(chunk);
+            Fr::from_slice(&bytes).unwrap()
+        }))
+        .collect()
+}
+
+/// Commits to an opaque byte string as `[len, chunk_0, ..]` over 31-byte chunks. Panics if the key
+/// holds fewer than `1 + ceil(len / 31)` generators
+pub fn commit_bytes(key: &CommitmentKey, msg: &[u8], r: Fr) -> AffineG1 {
+    key.commit(&bytes_to_fr(msg), r)
+}
+
+// 2^248, above every chunk and length, so each hash scalar is nonzero and stays injective
+const HASH_MARKER: &str = "452312848583266388373324160190187140051835877600158453279131187530910662656";
+
+/// Unblinded Pedersen hash: the `commit_bytes` encoding, each scalar offset by 2^248, against generators
+/// hashed under `dst` with their own tag byte, so they never coincide with commitment generators even
+/// under the same DST. Collision resistant under discrete log, not hiding. Panics if `dst` is empty
+pub fn pedersen_hash(msg: &[u8], dst: &[u8]) -> AffineG1 {
+    let marker = Fr::from_str(HASH_MARKER).unwrap();
+    let scalars: Vec<Fr> = bytes_to_fr(msg).into_iter().map(|s| s + marker).collect();
+    let points: Vec<AffineG1> = (0..scalars.len())
+        .map(|i| AffineG1::hash(&[&[HASH_TAG], &(i as u64).to_le_bytes()[..]].concat(), dst))
+        .collect();
+    msm(&points, &scalars)
+}
+
+const BLINDING_DST: &[u8] = b"PEDERSEN-DETERMINISTIC-BLINDING-V01";
+
+// Smallest counter whose candidate is nonzero; a zero blinding would leave the commitment unhidden
+fn first_nonzero(mut candidate: impl FnMut(u8) -> Fr) -> Fr {
+    (0..=u8::MAX).map(&mut candidate).find(|r| !r.is_zero()).expect("256 consecutive zero blindings")
+}
+
+/// Blinding as a PRF of a secret `seed` and the committed data, in the spirit of RFC 6979, so a
+/// re-run prover reproduces its commitments. Every input is length-prefixed; a trailing counter byte
+/// re-expands in the (negligible) event of a zero output
+pub fn derive_blinding(seed: &[u8], context: &[u8], values: &[Fr]) -> Fr {
+    let mut input = Vec::with_capacity(24 + seed.len() + context.len() + 32 * values.len() + 1);
+    input.extend((seed.len() as u64).to_be_bytes());
+    input.extend(seed);
+    input.extend((context.len() as u64).to_be_bytes());
+    input.extend(context);
+    input.extend((values.len() as u64).to_be_bytes());
+    values.iter().for_each(|&v| input.extend(fr_to_bytes(v)));
+
+    first_nonzero(|ctr| {
+        let input = [&input[..], &[ctr]].concat();
+        Fr::hash_to_field(&input, BLINDING_DST, 1).expect("Failed to derive blinding")[0]
+    })
+}
+
+/// `key.commit` with the blinding from `derive_blinding`
+pub fn commit_deterministic(key: &CommitmentKey, seed: &[u8], context: &[u8], values: &[Fr]) -> AffineG1 {
+    key.commit(values, derive_blinding(seed, context, values))
+}
+
+/// Streams values into a commitment one at a time, keeping only the running sum of v_i·H_i
+pub struct CommitBuilder<'a> {
+    key: &'a CommitmentKey,
+    acc: G1,
+    next: usize,
+    filled: std::collections::HashSet<usize>,
+}
+
+impl<'a> CommitBuilder<'a> {
+    pub fn new(key: &'a CommitmentKey) -> Self {
+        CommitBuilder { key, acc: G1::zero(), next: 0, filled: std::collections::HashSet::new() }
+    }
+
+    /// Places `v` at the position after the last `push`; `push_at` does not move that position
+    pub fn push(&mut self, v: Fr) -> Result<(), CommitmentError> {
+        self.push_at(self.next, v)?;
+        self.next += 1;
+        Ok(())
+    }
+
+    /// Each position can be set once; unset positions commit to zero
+    pub fn push_at(&mut self, index: usize, v: Fr) -> Result<(), CommitmentError> {
+        let h = *self.key.table.inner.get(index).ok_or(CommitmentError::IndexOutOfRange { index, len: self.key.len() })?;
+        if !self.filled.insert(index) {
+            return Err(CommitmentError::DuplicateIndex(index));
+        }
+        self.acc = self.acc + G1::from(h) * v;
+        Ok(())
+    }
+
+    pub fn finalize(self, r: Fr) -> AffineG1 {
+        (self.acc + G1::from(self.key.table.generator) * r).into()
+    }
+}
+
+// Value generators shared by the free `commit`, one vector per DST. Bounded in both directions so
+// callers cycling through DSTs or lengths can't grow it without limit; past either bound the
+// generators are hashed on every call instead
+const MAX_CACHED_DSTS: usize = 64;
+const MAX_CACHED_LEN: usize = 1 << 16;
+static GENERATORS: Lazy<Mutex<HashMap<Vec<u8>, Vec<AffineG1>>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+// The cache only ever holds finished generators, so a panic on another thread while it was
+// locked leaves nothing half-written and the poison can be ignored
+fn cached_generators() -> MutexGuard<'static, HashMap<Vec<u8>, Vec<AffineG1>>> {
+    GENERATORS.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+// First `n` value generators for `dst`. Missing ones are hashed with the lock released, so other
+// DSTs aren't held up; two threads racing on the same DST both hash and the longer result is kept
+fn value_generators(n: usize, dst: &[u8]) -> Vec<AffineG1> {
+    let mut generators = cached_generators().get(dst).map(|g| g[..n.min(g.len())].to_vec()).unwrap_or_default();
+    let cached = generators.len();
+    if cached == n {
+        return generators;
+    }
+    generators.extend(hash_value_generators(cached..n, dst));
+
+    if n <= MAX_CACHED_LEN {
+        let mut cache = cached_generators();
+        if cache.len() < MAX_CACHED_DSTS || cache.contains_key(dst) {
+            let entry = cache.entry(dst.to_vec()).or_default();
+            if entry.len() < n {
+                *entry = generators.clone();
+            }
+        }
+    }
+    generators
+}
+
+// Pedersen-style vector commitment. Value generators for `params.dst` are cached process-wide, so
+// each one is usually only hashed the first time it is needed. Values can come from any iterator;
+// they are gathered once for the MSM, which needs all scalars up front
+pub fn commit<I: IntoIterator<Item = Fr>>(vs: I, params: &CommitmentParams, r: Fr) -> AffineG1 {
+    let vs: Vec<Fr> = vs.into_iter().collect();
+    let generators = value_generators(vs.len(), &params.dst);
+    commit_with_generators(&vs, params.generator, &generators, r)
+}
+
+/// G2 counterpart of `CommitmentKey`: the value generators are hashed under the key's DST once, up
+/// front. Each G2 operation costs roughly three times its G1 counterpart, so prefer `CommitmentKey`
+/// unless the protocol needs the commitment on the G2 side
+pub struct CommitmentKeyG2 {
+    generator: AffineG2,
+    inner: Vec<AffineG2>,
+}
+
+impl CommitmentKeyG2 {
+    /// `n` value generators hashed under `dst` and the blinding base `g`. Only sound if nobody knows
+    /// the discrete log of `g` relative to the hashed generators
+    pub fn new(n: usize, dst: &[u8], g: AffineG2) -> Result<Self, HashToCurveError> {
+        if dst.is_empty() {
+            return Err(HashToCurveError::InvalidDst);
+        }
+        let inner = (0..n).map(|i| AffineG2::hash(&generator_input(i), dst)).collect();
+        Ok(CommitmentKeyG2 { generator: g, inner })
+    }
+
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+}
+
+/// Pedersen-style vector commitment in G2. Panics if `vs` is longer than the key
+pub fn commit_g2(key: &CommitmentKeyG2, vs: &[Fr], r: Fr) -> AffineG2 {
+    assert!(vs.len() <= key.len(), "CommitmentKeyG2 holds {} generators but {} values were given", key.len(), vs.len());
+    let points: Vec<AffineG2> = std::iter::once(key.generator).chain(key.inner[..vs.len()].iter().copied()).collect();
+    let scalars: Vec<Fr> = std::iter::once(r).chain(vs.iter().copied()).collect();
+    msm_g2(&points, &scalars)
+}
+
+// Compared through the canonical encoding so the equality check itself doesn't short-circuit
+pub fn verify_commit(commitment: AffineG1, vs: &[Fr], r: Fr, params: &CommitmentParams) -> bool {
+    let expected = commit(vs.iter().copied(), params, r);
+    expected.to_uncompressed().ct_eq(&commitment.to_uncompressed()).into()
+}
+
+/// The values and blinding factor a commitment was computed from
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Opening {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde::fr_vec"))]
+    pub values: Vec<Fr>,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde::fr"))]
+    pub blinding: Fr,
+}
+
+pub fn open(commitment: AffineG1, opening: &Opening, params: &CommitmentParams) -> bool {
+    verify_commit(commitment, &opening.values, opening.blinding, params)
+}
+
+/// Recomputes the commitment under `key` and compares encodings in constant time. An opening longer
+/// than the key cannot have come from it and is rejected instead of panicking
+pub fn verify(key: &CommitmentKey, commitment: &AffineG1, opening: &Opening) -> bool {
+    if opening.values.len() > key.len() {
+        return false;
+    }
+    let expected = key.commit(&opening.values, opening.blinding);
+    expected.to_uncompressed().ct_eq(&commitment.to_uncompressed()).into()
+}
+
+// substrate_bn's field types don't implement Zeroize, so overwrite them with zero and keep the store
+// observable through black_box, which stops it being elided as dead before the drop
+#[cfg(feature = "zeroize")]
+impl zeroize::Zeroize for Opening {
+    /// Keeps the length, so a wiped opening is the all-zero opening of the same size
+    fn zeroize(&mut self) {
+        self.values.iter_mut().for_each(|v| crate::wipe(v, Fr::zero()));
+        crate::wipe(&mut self.blinding, Fr::zero());
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for Opening {
+    fn drop(&mut self) {
+        zeroize::Zeroize::zeroize(self);
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::ZeroizeOnDrop for Opening {}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::Zeroize for CommitBuilder<'_> {
+    // The running sum is a function of the values pushed so far
+    fn zeroize(&mut self) {
+        crate::wipe(&mut self.acc, G1::zero());
+        self.filled.clear();
+        self.next = 0;
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for CommitBuilder<'_> {
+    fn drop(&mut self) {
+        zeroize::Zeroize::zeroize(self);
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::ZeroizeOnDrop for CommitBuilder<'_> {}
+
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct KeyRepr {
+    #[serde(with = "crate::serde::bytes")]
+    dst: Vec<u8>,
+    #[serde(with = "crate::serde::g1_compressed")]
+    generator: AffineG1,
+    len: u64,
+}
+
+// Loading hashes every value generator, one hash-to-curve each, so an untrusted length is capped.
+// At 2^16 a hostile key costs a few seconds to load rather than minutes
+#[cfg(feature = "serde")]
+const MAX_SERIALIZED_LEN: u64 = 1 << 16;
+
+// The DST, blinding generator and length are stored. Loading re-derives every generator from the
+// DST, so only keys whose blinding generator is `blinding_generator(dst)` can be written; the
+// stored one is a check that the reader derives the same point
+#[cfg(feature = "serde")]
+impl serde::Serialize for CommitmentKey {
+    fn serialize<S: serde::Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        if self.dst.is_empty() {
+            return Err(serde::ser::Error::custom("imported CommitmentKey has no DST to re-derive from"));
+        }
+        if self.table.generator != blinding_generator(&self.dst) {
+            return Err(serde::ser::Error::custom("CommitmentKey blinding generator is not derived from its DST"));
+        }
+        let repr = KeyRepr { dst: self.dst.clone(), generator: self.table.generator, len: self.len() as u64 };
+        serde::Serialize::serialize(&repr, s)
+    }
+}
+
+// A blinding generator with a known discrete log would break binding, so one that differs from
+// the re-derived point is refused rather than trusted
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for CommitmentKey {
+    fn deserialize<D: serde::Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        let repr = <KeyRepr as serde::Deserialize>::deserialize(d)?;
+        if repr.len > MAX_SERIALIZED_LEN {
+            return Err(serde::de::Error::custom(format!("CommitmentKey length {} above {}", repr.len, MAX_SERIALIZED_LEN)));
+        }
+        let params = CommitmentParams::new(&repr.dst).map_err(serde::de::Error::custom)?;
+        if params.generator != repr.generator {
+            return Err(serde::de::Error::custom("CommitmentKey blinding generator is not derived from its DST"));
+        }
+        Ok(CommitmentKey::from_params(repr.len as usize, params))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::thread_rng;
+    use substrate_bn::G2;
+
+    const DST: &[u8] = b"QUUX-V01-CS02-with-BN254G1_XMD:SHA-256_SVDW_RO_";
+
+    #[test]
+    fn test_commit_additive_homomorphic() {
+        let mut rng = thread_rng();
+
+        let v1 = (0..10).map(|_| Fr::random(&mut rng)).collect::<Vec<_>>();
+        let v2 = (0..10).map(|_| Fr::random(&mut rng)).collect::<Vec<_>>();
+        let v_sum: Vec<Fr> = v1.iter().zip(&v2).map(|(&a, &b)| a + b).collect();
+
+        let r1 = Fr::random(&mut rng);
+        let r2 = Fr::random(&mut rng);
+        let r_sum = r1 + r2;
+
+        let params = CommitmentParams::new(DST).unwrap();
+
+        let c1 = commit(v1.iter().copied(), &params, r1);
+        let c2 = commit(v2.iter().copied(), &params, r2);
+        let c_sum = commit(v_sum.iter().copied(), &params, r_sum);
+
+        assert_eq!(c_sum, c1 + c2);
+    }
+
+    #[test]
+    fn test_commit_scalar_multiplication_homomorphic() {
+        let mut rng = thread_rng();
+
+        let v = (0..10).map(|_| Fr::random(&mut rng)).collect::<Vec<_>>();
+        let scalar = Fr::random(&mut rng);
+        let v_scaled: Vec<Fr> = v.iter().map(|&x| x * scalar).collect();
+
+        let r = Fr::random(&mut rng);
+        let params = CommitmentParams::new(DST).unwrap();
+
+        let c = commit(v.iter().copied(), &params, r);
+        let c_scaled = commit(v_scaled.iter().copied(), &params, r * scalar);
+
+        assert_eq!(c_scaled, c * scalar);
+    }
+
+    #[test]
+    fn test_commit_lazy_iterator() {
+        let params = CommitmentParams::new(DST).unwrap();
+        let r = Fr::random(&mut thread_rng());
+
+        let lazy = (1..=5u64).map(|i| Fr::from_str(&(i * i).to_string()).unwrap());
+        let dense: Vec<Fr> = lazy.clone().collect();
+        assert_eq!(commit(lazy, &params, r), commit(dense.iter().copied(), &params, r));
+    }
+
+    #[test]
+    fn test_generator_cache() {
+        let dst = b"generator-cache-test";
+        assert_eq!(value_generators(3, dst), GeneratorTable::new(3, dst).inner);
+        // A shorter request reads the cached prefix, a longer one extends it
+        assert_eq!(value_generators(2, dst)[..], value_generators(5, dst)[..2]);
+        assert_eq!(cached_generators()[&dst[..]].len(), 5);
+
+        for i in 0..MAX_CACHED_DSTS + 4 {
+            value_generators(1, format!("generator-cache-test-{}", i).as_bytes());
+        }
+        assert!(cached_generators().len() <= MAX_CACHED_DSTS);
+        assert_eq!(value_generators(1, b"generator-cache-test-overflow"), GeneratorTable::new(1, b"generator-cache-test-overflow").inner);
+
+        // A panic while the cache is locked doesn't take the free `commit` down with it
+        let _ = std::thread::spawn(|| {
+            let _guard = cached_generators();
+            panic!("poisoning the generator cache");
+        })
+        .join();
+        assert!(GENERATORS.is_poisoned());
+        assert_eq!(value_generators(6, dst), GeneratorTable::new(6, dst).inner);
+    }
+
+    #[test]
+    fn test_commitment_params() {
+        assert!(matches!(CommitmentParams::new(b""), Err(HashToCurveError::InvalidDst)));
+        // Longer DSTs are hashed down as RFC 9380 section 5.3.3 allows
+        assert!(CommitmentParams::new(&[b'a'; 256]).is_ok());
+
+        // Distinct DSTs give unrelated generators
+        let a = CommitmentParams::new(b"app-a").unwrap();
+        let b = CommitmentParams::new(b"app-b").unwrap();
+        assert!(a.generator != b.generator);
+        assert!(commit([Fr::one()], &a, Fr::zero()) != commit([Fr::one()], &b, Fr::zero()));
+    }
+
+    // Pins the commitment scheme; changing any of these breaks every existing commitment
+    #[test]
+    #[cfg(not(feature = "legacy-generators"))]
+    fn test_generator_vectors() {
+        use substrate_bn::Fq;
+
+        let table = GeneratorTable::new(2, DST);
+        assert!(table.generator == AffineG1::new(Fq::from_str("19107524966857306226786762860558186827451008626898296007943055600142878887585").unwrap(), Fq::from_str("18703555250678277211404746801406790631014746872533574158694750523123217529014").unwrap()).unwrap());
+        assert!(table.inner[0] == AffineG1::new(Fq::from_str("12996778491594548542200036536429244180481519409384359490012802328031419098066").unwrap(), Fq::from_str("19007540210392758070586423063884449010754290254253983416474810524304000370141").unwrap()).unwrap());
+        assert!(table.inner[1] == AffineG1::new(Fq::from_str("20559398101170786354496984775163431893464447797977174491008847810963012491649").unwrap(), Fq::from_str("11008763642513677290519062095082461326304412452176818844500705529081657165970").unwrap()).unwrap());
+
+        assert_eq!(generator_input(1), [1, 1, 0, 0, 0, 0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn test_blinding_generator_independent() {
+        let h = blinding_generator(DST);
+        assert_eq!(CommitmentParams::new(DST).unwrap().generator, h);
+
+        let table = GeneratorTable::new(256, DST);
+        assert_eq!(table.generator, h);
+        assert!(table.inner.iter().all(|&h_i| h_i != h));
+    }
+
+    #[test]
+    fn test_explicit_blinding_generator() {
+        let mut rng = thread_rng();
+        let g: AffineG1 = (G1::one() * Fr::random(&mut rng)).into();
+        let key = CommitmentKey::with_blinding_generator(2, DST, g).unwrap();
+
+        let values = [Fr::random(&mut rng), Fr::random(&mut rng)];
+        let r = Fr::random(&mut rng);
+        let params = CommitmentParams::with_generator(DST, g).unwrap();
+        assert_eq!(key.commit(&values, r), commit(values.iter().copied(), &params, r));
+        assert!(key.commit(&values, r) != CommitmentKey::new(2, DST).unwrap().commit(&values, r));
+    }
+
+    #[test]
+    fn test_verify_commit() {
+        let mut rng = thread_rng();
+        let params = CommitmentParams::new(DST).unwrap();
+
+        let values = (0..4).map(|_| Fr::random(&mut rng)).collect::<Vec<_>>();
+        let blinding = Fr::random(&mut rng);
+        let c = commit(values.iter().copied(), &params, blinding);
+        let opening = Opening { values, blinding };
+
+        assert!(verify_commit(c, &opening.values, opening.blinding, &params));
+        assert!(open(c, &opening, &params));
+
+        let mut tampered = opening.values.clone();
+        tampered[2] = tampered[2] + Fr::one();
+        assert!(!verify_commit(c, &tampered, opening.blinding, &params));
+        assert!(!verify_commit(c, &opening.values, opening.blinding + Fr::one(), &params));
+        assert!(!verify_commit(c, &opening.values[..3], opening.blinding, &params));
+    }
+
+    #[test]
+    fn test_verify_with_key() {
+        let mut rng = thread_rng();
+        let key = CommitmentKey::new(4, DST).unwrap();
+
+        let values = (0..4).map(|_| Fr::random(&mut rng)).collect::<Vec<_>>();
+        let blinding = Fr::random(&mut rng);
+        let c = key.commit(&values, blinding);
+        let opening = Opening { values, blinding };
+        assert!(verify(&key, &c, &opening));
+
+        let wrong_blinding = Opening { values: opening.values.clone(), blinding: blinding + Fr::one() };
+        assert!(!verify(&key, &c, &wrong_blinding));
+
+        let mut flipped = Opening { values: opening.values.clone(), blinding };
+        flipped.values[1] = -flipped.values[1];
+        assert!(!verify(&key, &c, &flipped));
+
+        let truncated = Opening { values: opening.values[..3].to_vec(), blinding };
+        assert!(!verify(&key, &c, &truncated));
+
+        // Longer than the key: rejected without panicking
+        let extended = Opening { values: [opening.values.clone(), vec![Fr::zero()]].concat(), blinding };
+        assert!(!verify(&key, &c, &extended));
+    }
+
+    #[test]
+    fn test_rerandomize() {
+        let mut rng = thread_rng();
+        let key = CommitmentKey::new(3, DST).unwrap();
+        let values = (0..3).map(|_| Fr::random(&mut rng)).collect::<Vec<_>>();
+        let (r, delta) = (Fr::random(&mut rng), Fr::random(&mut rng));
+
+        let c = rerandomize(key.commit(&values, r), delta, key.table.generator);
+        assert_eq!(c, key.commit(&values, r + delta));
+    }
+
+    #[test]
+    fn test_update_at() {
+        let mut rng = thread_rng();
+        let key = CommitmentKey::new(4, DST).unwrap();
+        let mut values = (0..4).map(|_| Fr::random(&mut rng)).collect::<Vec<_>>();
+        let r = Fr::random(&mut rng);
+        let c = key.commit(&values, r);
+
+        let new_value = Fr::random(&mut rng);
+        let updated = update_at(&key, c, 2, values[2], new_value).unwrap();
+        values[2] = new_value;
+        assert!(verify(&key, &updated, &Opening { values: values.clone(), blinding: r }));
+
+        assert_eq!(update_at(&key, c, 1, values[1], values[1]).unwrap(), c);
+        assert_eq!(update_at(&key, c, 4, Fr::zero(), Fr::one()), Err(CommitmentError::IndexOutOfRange { index: 4, len: 4 }));
+    }
+
+    #[test]
+    fn test_commit_sparse() {
+        let mut rng = thread_rng();
+        let key = CommitmentKey::new(64, DST).unwrap();
+        let r = Fr::random(&mut rng);
+
+        let entries = [(3, Fr::random(&mut rng)), (40, Fr::random(&mut rng)), (17, Fr::random(&mut rng))];
+        let mut dense = vec![Fr::zero(); 64];
+        for &(i, v) in &entries {
+            dense[i] = v;
+        }
+        assert_eq!(commit_sparse(&key, &entries, r).unwrap(), key.commit(&dense, r));
+
+        assert_eq!(commit_sparse(&key, &[], r).unwrap(), AffineG1::from(G1::from(key.table.generator) * r));
+        assert_eq!(commit_sparse(&key, &[(5, Fr::one()), (5, Fr::one())], r), Err(CommitmentError::DuplicateIndex(5)));
+        assert_eq!(commit_sparse(&key, &[(64, Fr::one())], r), Err(CommitmentError::IndexOutOfRange { index: 64, len: 64 }));
+    }
+
+    #[test]
+    fn test_commit_builder() {
+        let mut rng = thread_rng();
+        let key = CommitmentKey::new(5, DST).unwrap();
+        let values = (0..5).map(|_| Fr::random(&mut rng)).collect::<Vec<_>>();
+        let r = Fr::random(&mut rng);
+
+        // Interleaved: positions 0, 1 in order, 4 early, then 2, 3
+        let mut builder = CommitBuilder::new(&key);
+        builder.push(values[0]).unwrap();
+        builder.push_at(4, values[4]).unwrap();
+        builder.push(values[1]).unwrap();
+        builder.push(values[2]).unwrap();
+        builder.push_at(3, values[3]).unwrap();
+        assert_eq!(builder.push_at(4, Fr::one()), Err(CommitmentError::DuplicateIndex(4)));
+        assert_eq!(builder.push_at(5, Fr::one()), Err(CommitmentError::IndexOutOfRange { index: 5, len: 5 }));
+        let c = builder.finalize(r);
+        assert_eq!(c, key.commit(&values, r));
+
+        // Builder output adds like a batch commitment
+        let other = (0..5).map(|_| Fr::random(&mut rng)).collect::<Vec<_>>();
+        let mut builder = CommitBuilder::new(&key);
+        other.iter().for_each(|&v| builder.push(v).unwrap());
+        assert_eq!(builder.push(Fr::one()), Err(CommitmentError::IndexOutOfRange { index: 5, len: 5 }));
+        let sum: Vec<Fr> = values.iter().zip(&other).map(|(&a, &b)| a + b).collect();
+        assert_eq!(c + builder.finalize(r), key.commit(&sum, r + r));
+    }
+
+    #[test]
+    fn test_bytes_to_fr_injective() {
+        let cases: [&[u8]; 8] = [b"", &[0], &[0, 0], &[1], &[1, 0], &[0u8; 31], &[0u8; 32], &[0u8; 62]];
+        for (i, a) in cases.iter().enumerate() {
+            for b in &cases[i + 1..] {
+                assert!(bytes_to_fr(a) != bytes_to_fr(b));
+            }
+        }
+
+        // Lengths one chunk apart, with the extra chunk all zero
+        let msg = [7u8; 31];
+        assert!(bytes_to_fr(&msg) != bytes_to_fr(&[&msg[..], &[0u8; 31]].concat()));
+        assert_eq!(bytes_to_fr(&[0xff; 31])[1], Fr::from_slice(&[[0u8].as_slice(), &[0xff; 31]].concat()).unwrap());
+    }
+
+    #[test]
+    fn test_commit_bytes() {
+        let key = CommitmentKey::new(4, DST).unwrap();
+        let r = Fr::random(&mut thread_rng());
+        let msg = b"a user record longer than a single thirty-one byte chunk";
+
+        assert_eq!(commit_bytes(&key, msg, r), key.commit(&bytes_to_fr(msg), r));
+        assert!(commit_bytes(&key, msg, r) != commit_bytes(&key, &[&msg[..], &[0]].concat(), r));
+        assert!(commit_bytes(&key, b"", r) != commit_bytes(&key, &[0], r));
+    }
+
+    fn batch_items(n: usize) -> (CommitmentKey, Vec<(AffineG1, Opening)>) {
+        let mut rng = thread_rng();
+        let key = CommitmentKey::new(6, DST).unwrap();
+        let items = (0..n)
+            .map(|j| {
+                // Lengths vary from 1 to 6 across the batch
+                let values = (0..1 + j % 6).map(|_| Fr::random(&mut rng)).collect::<Vec<_>>();
+                let blinding = Fr::random(&mut rng);
+                (key.commit(&values, blinding), Opening { values, blinding })
+            })
+            .collect();
+        (key, items)
+    }
+
+    #[test]
+    fn test_verify_batch() {
+        let (key, mut items) = batch_items(50);
+        assert!(verify_batch(&key, &items));
+        assert!(verify_batch_with_rng(&key, &items, &mut thread_rng()));
+
+        items[23].1.values[0] = items[23].1.values[0] + Fr::one();
+        assert!(!verify_batch(&key, &items));
+        assert!(!verify_batch_with_rng(&key, &items, &mut thread_rng()));
+    }
+
+    // Past the 170 scalars a single XMD expansion can produce
+    #[test]
+    fn test_verify_batch_large() {
+        let (key, mut items) = batch_items(200);
+        assert!(verify_batch(&key, &items));
+
+        items[180].1.blinding = items[180].1.blinding + Fr::one();
+        assert!(!verify_batch(&key, &items));
+    }
+
+    #[test]
+    fn test_verify_batch_edge_cases() {
+        let (key, mut items) = batch_items(3);
+        assert!(verify_batch(&key, &[]));
+
+        items[1].1.blinding = items[1].1.blinding + Fr::one();
+        assert!(!verify_batch(&key, &items));
+
+        // Longer than the key: false, not a panic
+        let (_, mut items) = batch_items(3);
+        items[0].1.values = vec![Fr::one(); 7];
+        assert!(!verify_batch(&key, &items));
+    }
+
+    #[test]
+    fn test_verify_batch_deterministic() {
+        let (key, items) = batch_items(5);
+        let weights = batch_weights(&items).unwrap();
+        assert!(weights == batch_weights(&items).unwrap());
+        assert!(weights[..4] != batch_weights(&items[..4]).unwrap()[..]);
+        assert!(verify_batch_weighted(&key, &items, &weights));
+        assert_eq!(verify_batch(&key, &items), verify_batch(&key, &items));
+    }
+
+    #[test]
+    #[cfg(feature = "zeroize")]
+    fn test_zeroize() {
+        use zeroize::{Zeroize, ZeroizeOnDrop};
+
+        fn assert_zeroize_on_drop<T: ZeroizeOnDrop>() {}
+        assert_zeroize_on_drop::<Opening>();
+        assert_zeroize_on_drop::<CommitBuilder<'_>>();
+
+        let mut rng = thread_rng();
+        let mut opening = Opening { values: (0..3).map(|_| Fr::random(&mut rng)).collect(), blinding: Fr::random(&mut rng) };
+        opening.zeroize();
+        assert!(opening.values == vec![Fr::zero(); 3]);
+        assert!(opening.blinding == Fr::zero());
+
+        let key = CommitmentKey::new(2, DST).unwrap();
+        let mut builder = CommitBuilder::new(&key);
+        builder.push(Fr::one()).unwrap();
+        builder.zeroize();
+        assert!(builder.acc.is_zero());
+        builder.push(Fr::one()).unwrap();
+    }
+
+    #[test]
+    fn test_derive_blinding() {
+        let values = [Fr::one(), Fr::from_str("2").unwrap()];
+        let r = derive_blinding(b"seed", b"ctx", &values);
+        assert!(r == derive_blinding(b"seed", b"ctx", &values));
+
+        // Each input moves the output; the length prefixes keep seed and context from sliding
+        assert!(r != derive_blinding(b"seed", b"ctx2", &values));
+        assert!(r != derive_blinding(b"seedc", b"tx", &values));
+        assert!(r != derive_blinding(b"seed2", b"ctx", &values));
+        assert!(r != derive_blinding(b"seed", b"ctx", &values[..1]));
+
+        let key = CommitmentKey::new(2, DST).unwrap();
+        assert_eq!(commit_deterministic(&key, b"seed", b"ctx", &values), key.commit(&values, r));
+        assert!(commit_deterministic(&key, b"seed", b"a", &values) != commit_deterministic(&key, b"seed", b"b", &values));
+    }
+
+    #[test]
+    fn test_derive_blinding_skips_zero() {
+        let r = first_nonzero(|ctr| if ctr < 2 { Fr::zero() } else { Fr::from_str(&ctr.to_string()).unwrap() });
+        assert!(r == Fr::from_str("2").unwrap());
+    }
+
+    #[test]
+    fn test_pedersen_hash_vector() {
+        use substrate_bn::Fq;
+
+        // Pins the hash for use as a long-term identifier
+        let h = pedersen_hash(b"abc", b"PEDERSEN-HASH-V01-TEST");
+        assert!(h == AffineG1::new(Fq::from_str("21392050817271640580020066401672558070490836972736840088500516318696319439133").unwrap(), Fq::from_str("2284353293187429987462620981976904626398855451159655545054714811573747954471").unwrap()).unwrap());
+    }
+
+    #[test]
+    fn test_pedersen_hash_collisions() {
+        let dst = b"PEDERSEN-HASH-V01-TEST";
+        let msg = [5u8; 40];
+        let cases: [&[u8]; 6] = [b"", &[0], &msg[..31], &msg[..32], &msg, &[&msg[..], &[0u8; 22]].concat()];
+        let hashes: Vec<AffineG1> = cases.iter().map(|m| pedersen_hash(m, dst)).collect();
+        for (i, a) in hashes.iter().enumerate() {
+            assert!(hashes[i + 1..].iter().all(|b| a != b));
+        }
+
+        // Separate from commitments under the same DST
+        let key = CommitmentKey::new(2, dst).unwrap();
+        assert!(pedersen_hash(b"abc", dst) != commit_bytes(&key, b"abc", Fr::zero()));
+        assert!(pedersen_hash(b"abc", dst) != pedersen_hash(b"abc", b"other"));
+    }
+
+    #[test]
+    fn test_aggregate() {
+        let mut rng = thread_rng();
+        let key = CommitmentKey::new(3, DST).unwrap();
+        let v1 = (0..3).map(|_| Fr::random(&mut rng)).collect::<Vec<_>>();
+        let v2 = (0..3).map(|_| Fr::random(&mut rng)).collect::<Vec<_>>();
+        let (r1, r2) = (Fr::random(&mut rng), Fr::random(&mut rng));
+        let sum: Vec<Fr> = v1.iter().zip(&v2).map(|(&a, &b)| a + b).collect();
+
+        let agg = aggregate(&[Commitment(key.commit(&v1, r1)), Commitment(key.commit(&v2, r2))]).unwrap();
+        assert_eq!(agg, Commitment(key.commit(&sum, r1 + r2)));
+
+        let keyed = KeyedCommitment::aggregate(&[key.commit_keyed(&v1, r1), key.commit_keyed(&v2, r2)]).unwrap();
+        assert_eq!(keyed.commitment, agg);
+        assert_eq!(keyed.fingerprint, key.fingerprint());
+
+        assert_eq!(aggregate(&[]), Err(CommitmentError::EmptyAggregate));
+        assert_eq!(KeyedCommitment::aggregate(&[]), Err(CommitmentError::EmptyAggregate));
+    }
+
+    #[test]
+    fn test_keyed_commitment_mismatch() {
+        let key = CommitmentKey::new(2, DST).unwrap();
+        let other_dst = CommitmentKey::new(2, b"other").unwrap();
+        let other_len = CommitmentKey::new(3, DST).unwrap();
+        assert_eq!(key.fingerprint(), CommitmentKey::new(2, DST).unwrap().fingerprint());
+        assert!(key.fingerprint() != other_dst.fingerprint());
+        assert!(key.fingerprint() != other_len.fingerprint());
+
+        let c = key.commit_keyed(&[Fr::one()], Fr::one());
+        let d = other_dst.commit_keyed(&[Fr::one()], Fr::one());
+        assert_eq!(KeyedCommitment::aggregate(&[c, d]), Err(CommitmentError::FingerprintMismatch));
+
+        // Same DST and length, different blinding generator
+        let g: AffineG1 = (G1::one() * Fr::random(&mut thread_rng())).into();
+        let other_generator = CommitmentKey::with_blinding_generator(2, DST, g).unwrap();
+        assert!(key.fingerprint() != other_generator.fingerprint());
+        let e = other_generator.commit_keyed(&[Fr::one()], Fr::one());
+        assert_eq!(KeyedCommitment::aggregate(&[c, e]), Err(CommitmentError::FingerprintMismatch));
+
+        // The fingerprint costs 8 bytes on top of the compressed point
+        assert_eq!(c.to_bytes().len(), 32 + 8);
+        assert_eq!(c.to_bytes()[..32], c.commitment.0.to_compressed());
+    }
+
+    #[test]
+    fn test_imported_key_fingerprint() {
+        let mut rng = thread_rng();
+        let random_points = |rng: &mut rand::rngs::ThreadRng| (0..3).map(|_| (G1::one() * Fr::random(rng)).into()).collect::<Vec<AffineG1>>();
+        let a = CommitmentKey::from_points(random_points(&mut rng)).unwrap();
+        let b = CommitmentKey::from_points(random_points(&mut rng)).unwrap();
+        assert_eq!(a.len(), b.len());
+        assert!(a.fingerprint() != b.fingerprint());
+        assert_eq!(a.fingerprint(), CommitmentKey::from_points(a.export().iter().map(|p| AffineG1::from_uncompressed(p).unwrap()).collect()).unwrap().fingerprint());
+
+        let c = a.commit_keyed(&[Fr::one()], Fr::one());
+        let d = b.commit_keyed(&[Fr::one()], Fr::one());
+        assert_eq!(KeyedCommitment::aggregate(&[c, d]), Err(CommitmentError::FingerprintMismatch));
+    }
+
+    #[test]
+    fn test_export_round_trip() {
+        let mut rng = thread_rng();
+        let key = CommitmentKey::new(4, DST).unwrap();
+        let exported = key.export();
+        assert_eq!(exported.len(), 5);
+        assert_eq!(exported[0], key.table.generator.to_uncompressed());
+
+        // Pinned so the representation handed to external verifiers can't drift
+        assert_eq!(hex::encode(exported[1]), "1cbbea7b3391e3f7f3e6bdb8c58b03bbdf05589b3a5bbf4a7a05266ef802e3d22a05e2f29409b353ae7b855d5ed3aac3e43548f65ac7d4af997d4c4317eb45dd");
+
+        let points = exported.iter().map(|b| AffineG1::from_uncompressed(b).unwrap()).collect();
+        let imported = CommitmentKey::from_points(points).unwrap();
+        assert_eq!(imported.export(), exported);
+
+        let vs = (0..4).map(|_| Fr::random(&mut rng)).collect::<Vec<_>>();
+        let r = Fr::random(&mut rng);
+        assert_eq!(imported.commit(&vs, r), key.commit(&vs, r));
+    }
+
+    #[test]
+    fn test_export_text() {
+        let key = CommitmentKey::new(2, DST).unwrap();
+        let lines: Vec<&str> = key.export_hex().lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[1], hex::encode(key.export()[1]));
+
+        let json = key.export_json();
+        assert!(json.starts_with("[\"0x") && json.ends_with("\"]"));
+        assert_eq!(json.matches("0x").count(), 3);
+        assert!(json.contains(&format!("\"0x{}\"", lines[2])));
+    }
+
+    #[test]
+    fn test_from_points_rejects_missing_blinding_generator() {
+        // AffineG1::new already refuses off-curve points, so an empty import is the reachable case
+        assert_eq!(CommitmentKey::from_points(vec![]).err(), Some(CommitmentError::InvalidGenerator(0)));
+        assert_eq!(CommitmentKey::from_points(vec![G1::one().into()]).unwrap().len(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "imported CommitmentKey")]
+    fn test_imported_key_cannot_grow() {
+        let mut key = CommitmentKey::from_points(vec![G1::one().into(); 3]).unwrap();
+        key.ensure_len(2);
+        key.ensure_len(3);
+    }
+
+    #[test]
+    fn test_commit_g2_additive_homomorphic() {
+        let mut rng = thread_rng();
+
+        let v1 = (0..4).map(|_| Fr::random(&mut rng)).collect::<Vec<_>>();
+        let v2 = (0..4).map(|_| Fr::random(&mut rng)).collect::<Vec<_>>();
+        let v_sum: Vec<Fr> = v1.iter().zip(&v2).map(|(&a, &b)| a + b).collect();
+
+        let r1 = Fr::random(&mut rng);
+        let r2 = Fr::random(&mut rng);
+
+        let key = CommitmentKeyG2::new(4, DST, G2::one().into()).unwrap();
+
+        let c1 = commit_g2(&key, &v1, r1);
+        let c2 = commit_g2(&key, &v2, r2);
+        let c_sum = commit_g2(&key, &v_sum, r1 + r2);
+
+        assert!(G2::from(c_sum) == G2::from(c1) + G2::from(c2));
+    }
+
+    #[test]
+    fn test_commit_g2_scalar_multiplication_homomorphic() {
+        let mut rng = thread_rng();
+
+        let v = (0..4).map(|_| Fr::random(&mut rng)).collect::<Vec<_>>();
+        let scalar = Fr::random(&mut rng);
+        let v_scaled: Vec<Fr> = v.iter().map(|&x| x * scalar).collect();
+
+        let r = Fr::random(&mut rng);
+        let key = CommitmentKeyG2::new(4, DST, G2::one().into()).unwrap();
+
+        let c = commit_g2(&key, &v, r);
+        let c_scaled = commit_g2(&key, &v_scaled, r * scalar);
+
+        assert!(G2::from(c_scaled) == G2::from(c) * scalar);
+    }
+
+    #[test]
+    fn test_commit_g2_dst_separation() {
+        let g: AffineG2 = G2::one().into();
+        let a = CommitmentKeyG2::new(2, b"app-a", g).unwrap();
+        let b = CommitmentKeyG2::new(2, b"app-b", g).unwrap();
+        assert!(commit_g2(&a, &[Fr::one()], Fr::zero()) != commit_g2(&b, &[Fr::one()], Fr::zero()));
+        assert!(matches!(CommitmentKeyG2::new(2, b"", g), Err(HashToCurveError::InvalidDst)));
+    }
+
+    #[test]
+    fn test_commit_with_table() {
+        let mut rng = thread_rng();
+        let params = CommitmentParams::new(DST).unwrap();
+        let table = GeneratorTable::new(8, DST);
+        assert_eq!(table.len(), 8);
+
+        let values = (0..5).map(|_| Fr::random(&mut rng)).collect::<Vec<_>>();
+        let r = Fr::random(&mut rng);
+        assert_eq!(commit_with_table(&values, &table, r), commit(values.iter().copied(), &params, r));
+    }
+
+    #[test]
+    fn test_commitment_key() {
+        let mut rng = thread_rng();
+        let params = CommitmentParams::new(DST).unwrap();
+        let mut key = CommitmentKey::new(2, DST).unwrap();
+
+        let values = (0..6).map(|_| Fr::random(&mut rng)).collect::<Vec<_>>();
+        let r = Fr::random(&mut rng);
+        assert_eq!(key.commit(&values[..2], r), commit(values[..2].iter().copied(), &params, r));
+
+        // Growing keeps the existing prefix and matches a key built at full length
+        key.ensure_len(6);
+        assert_eq!(key.len(), 6);
+        assert_eq!(key.commit(&values, r), CommitmentKey::new(6, DST).unwrap().commit(&values, r));
+        assert_eq!(key.commit(&values, r), commit(values.iter().copied(), &params, r));
+
+        assert!(matches!(CommitmentKey::new(0, b""), Err(HashToCurveError::InvalidDst)));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_commit_with_table_too_short() {
+        let table = GeneratorTable::new(2, DST);
+        commit_with_table(&[Fr::one(); 3], &table, Fr::one());
+    }
+}
@@ -0,0 +1,142 @@
+use once_cell::sync::Lazy;
+use substrate_bn::{AffineG1, AffineG2, Fr, Group, G1, G2};
+
+use crate::{g1, g2};
+
+/// Curve checks done directly on the affine coordinates, independent of `substrate_bn`'s own validation
+pub trait PointValidation {
+    /// y² == x³ + b for the group's curve (b = 3 on G1, 3 / (9 + i) on the G2 twist)
+    fn is_on_curve(&self) -> bool;
+    /// (0, 0), the conventional affine stand-in for the point at infinity (EIP-196/197)
+    fn is_identity(&self) -> bool;
+}
+
+impl PointValidation for AffineG1 {
+    fn is_on_curve(&self) -> bool {
+        let (x, y) = (self.x(), self.y());
+        y * y == x * x * x + *g1::B
+    }
+
+    fn is_identity(&self) -> bool {
+        self.x().is_zero() && self.y().is_zero()
+    }
+}
+
+impl PointValidation for AffineG2 {
+    fn is_on_curve(&self) -> bool {
+        let (x, y) = (self.x(), self.y());
+        y * y == x * x * x + *g2::B
+    }
+
+    fn is_identity(&self) -> bool {
+        self.x().is_zero() && self.y().is_zero()
+    }
+}
+
+/// Membership in the prime-order subgroup of order r
+pub trait SubgroupCheck {
+    fn is_in_subgroup(&self) -> bool;
+}
+
+// [r]P == O, computed as [r - 1]P + P since r itself reduces to zero in Fr. G1 has prime order, so
+// for points on the curve this always agrees with `is_on_curve`; it is here for callers that
+// cannot rely on that
+impl SubgroupCheck for AffineG1 {
+    fn is_in_subgroup(&self) -> bool {
+        let p = G1::from(*self);
+        self.is_on_curve() && (p * (-Fr::one()) + p).is_zero()
+    }
+}
+
+// 6x² for the BN seed x = 4965661367192848881, which is p mod r: the eigenvalue of psi on G2
+static PSI_EIGENVALUE: Lazy<Fr> = Lazy::new(|| Fr::from_str("147946756881789318990833708069417712966").unwrap());
+
+// Endomorphism membership test: on the BN254 twist, P is in G2 exactly when psi(P) == [6x²]P. It
+// costs one 128-bit scalar multiplication instead of the full [r]P == O, which remains the textbook
+// fallback and is what the tests compare against. The multiplier is 6x², not x; psi(P) == [x]P is
+// the BLS12 form of the test
+impl SubgroupCheck for AffineG2 {
+    fn is_in_subgroup(&self) -> bool {
+        self.is_on_curve() && g2::psi(self) == AffineG2::from(G2::from(*self) * *PSI_EIGENVALUE)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{thread_rng, Rng};
+    use substrate_bn::{Fq, Fq2};
+
+    use crate::HashToCurve;
+
+    #[test]
+    fn test_generators_on_curve() {
+        let g: AffineG1 = G1::one().into();
+        assert!(g.is_on_curve() && !g.is_identity());
+        let g: AffineG2 = G2::one().into();
+        assert!(g.is_on_curve() && !g.is_identity());
+    }
+
+    #[test]
+    fn test_hash_outputs_on_curve() {
+        let mut rng = thread_rng();
+        for _ in 0..16 {
+            let msg: [u8; 32] = rng.gen();
+            assert!(AffineG1::hash_default(&msg).is_on_curve());
+            assert!(AffineG2::hash_default(&msg).is_on_curve());
+        }
+    }
+
+    #[test]
+    fn test_map_to_curve_outputs_on_curve() {
+        let mut rng = thread_rng();
+        for _ in 0..16 {
+            assert!(AffineG1::map_to_curve(Fq::random(&mut rng)).unwrap().is_on_curve());
+            assert!(AffineG2::map_to_curve(Fq2::new(Fq::random(&mut rng), Fq::random(&mut rng))).unwrap().is_on_curve());
+        }
+        // u = 0, where sgn0(u) = 0 fixes the sign of y
+        assert!(AffineG1::map_to_curve(Fq::zero()).unwrap().is_on_curve());
+        assert!(AffineG2::map_to_curve(Fq2::zero()).unwrap().is_on_curve());
+    }
+
+    #[test]
+    fn test_g1_is_in_subgroup() {
+        let mut rng = thread_rng();
+        assert!(AffineG1::from(G1::one()).is_in_subgroup());
+        for _ in 0..8 {
+            let msg: [u8; 32] = rng.gen();
+            assert!(AffineG1::hash_default(&msg).is_in_subgroup());
+            assert!(AffineG1::from(G1::one() * Fr::random(&mut rng)).is_in_subgroup());
+        }
+    }
+
+    fn r_torsion(p: AffineG2) -> bool {
+        let p = G2::from(p);
+        (p * (-Fr::one()) + p).is_zero()
+    }
+
+    #[test]
+    fn test_g2_is_in_subgroup() {
+        let mut rng = thread_rng();
+        assert!(AffineG2::from(G2::one()).is_in_subgroup());
+        for _ in 0..8 {
+            let msg: [u8; 32] = rng.gen();
+            assert!(AffineG2::hash_default(&msg).is_in_subgroup());
+            assert!(AffineG2::from(G2::one() * Fr::random(&mut rng)).is_in_subgroup());
+        }
+    }
+
+    #[test]
+    fn test_g2_subgroup_before_and_after_clear_cofactor() {
+        let mut rng = thread_rng();
+        for _ in 0..8 {
+            // A raw map_to_curve output lands in G2 with probability 1/h, about 2^-254
+            let q = AffineG2::map_to_curve(Fq2::new(Fq::random(&mut rng), Fq::random(&mut rng))).unwrap();
+            assert!(q.is_on_curve());
+            assert!(!q.is_in_subgroup() && !r_torsion(q));
+
+            let q = AffineG2::clear_cofactor(q);
+            assert!(q.is_in_subgroup() && r_torsion(q));
+        }
+    }
+}
@@ -0,0 +1,80 @@
+use substrate_bn::Fr;
+
+use crate::{msm, Commitment, CommitmentKey};
+
+/// Commits to the coefficients, constant term first
+pub fn commit_poly(key: &CommitmentKey, coeffs: &[Fr], r: Fr) -> Commitment {
+    Commitment(key.commit(coeffs, r))
+}
+
+/// Horner evaluation at `x`; the empty polynomial evaluates to zero
+pub fn eval(coeffs: &[Fr], x: Fr) -> Fr {
+    coeffs.iter().rev().fold(Fr::zero(), |acc, &c| acc * x + c)
+}
+
+/// Coefficients of sum(challenges[i] * polys[i]), padded to the longest polynomial
+pub fn combine(polys: &[&[Fr]], challenges: &[Fr]) -> Vec<Fr> {
+    assert_eq!(polys.len(), challenges.len(), "one challenge per polynomial");
+    let len = polys.iter().map(|p| p.len()).max().unwrap_or(0);
+    let mut out = vec![Fr::zero(); len];
+    for (poly, &ch) in polys.iter().zip(challenges) {
+        for (o, &c) in out.iter_mut().zip(poly.iter()) {
+            *o = *o + ch * c;
+        }
+    }
+    out
+}
+
+// Commitment to `combine(polys, challenges)` with blinding sum(challenges[i] * r_i), taken as one MSM
+// over the existing commitments instead of recommitting to the combined coefficients
+pub fn commit_eval_combination(commitments: &[Commitment], challenges: &[Fr]) -> Commitment {
+    assert_eq!(commitments.len(), challenges.len(), "one challenge per commitment");
+    let points: Vec<_> = commitments.iter().map(|c| c.0).collect();
+    Commitment(msm(&points, challenges))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::thread_rng;
+
+    const DST: &[u8] = b"QUUX-V01-CS02-with-BN254G1_XMD:SHA-256_SVDW_RO_";
+
+    fn fr(n: u64) -> Fr {
+        Fr::from_str(&n.to_string()).unwrap()
+    }
+
+    #[test]
+    fn test_eval() {
+        // 3 + 2x + x² at x = 5
+        assert_eq!(eval(&[fr(3), fr(2), fr(1)], fr(5)), fr(38));
+        assert_eq!(eval(&[fr(7)], fr(5)), fr(7));
+        assert_eq!(eval(&[], fr(5)), Fr::zero());
+    }
+
+    #[test]
+    fn test_combine() {
+        let a = [fr(1), fr(2), fr(3)];
+        let b = [fr(4)];
+        assert_eq!(combine(&[&a, &b], &[fr(2), fr(3)]), vec![fr(14), fr(4), fr(6)]);
+
+        let x = fr(11);
+        assert_eq!(eval(&combine(&[&a, &b], &[fr(2), fr(3)]), x), fr(2) * eval(&a, x) + fr(3) * eval(&b, x));
+    }
+
+    #[test]
+    fn test_commit_eval_combination() {
+        let mut rng = thread_rng();
+        let key = CommitmentKey::new(6, DST).unwrap();
+
+        let polys: Vec<Vec<Fr>> = [6, 3, 1, 6].iter().map(|&n| (0..n).map(|_| Fr::random(&mut rng)).collect()).collect();
+        let polys: Vec<&[Fr]> = polys.iter().map(|p| p.as_slice()).collect();
+        let blindings: Vec<Fr> = polys.iter().map(|_| Fr::random(&mut rng)).collect();
+        let challenges: Vec<Fr> = polys.iter().map(|_| Fr::random(&mut rng)).collect();
+
+        let commitments: Vec<Commitment> = polys.iter().zip(&blindings).map(|(p, &r)| commit_poly(&key, p, r)).collect();
+        let r = blindings.iter().zip(&challenges).fold(Fr::zero(), |acc, (&r, &ch)| acc + r * ch);
+        let direct = commit_poly(&key, &combine(&polys, &challenges), r);
+        assert_eq!(commit_eval_combination(&commitments, &challenges), direct);
+    }
+}
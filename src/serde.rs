@@ -0,0 +1,367 @@
+// `#[serde(with = "sp1_hash2curve::serde::g1")]` modules for the `substrate_bn` types, which the
+// orphan rule keeps from implementing `Serialize` here. Human-readable formats get 0x-prefixed
+// big-endian hex: `[x, y]` for G1, `[[x.c0, x.c1], [y.c0, y.c1]]` for G2 and one string for `Fr`.
+// Binary formats get the 64, 128 and 32-byte encodings. `g1_compressed` and `g2_compressed` use the
+// 32 and 64-byte compressed encodings instead, as one hex string when human-readable
+
+use alloc::{format, string::String, vec::Vec};
+use core::fmt;
+
+use ::serde::de::{Error, SeqAccess, Visitor};
+use ::serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+fn to_hex(bytes: &[u8]) -> String {
+    format!("0x{}", hex::encode(bytes))
+}
+
+fn from_hex<E: Error>(s: &str) -> Result<[u8; 32], E> {
+    let bytes = hex::decode(s.strip_prefix("0x").unwrap_or(s)).map_err(E::custom)?;
+    bytes.try_into().map_err(|_| E::custom("expected 32 bytes of hex"))
+}
+
+// Accepts both `serialize_bytes` output and a plain sequence of bytes, so formats that encode byte
+// strings as arrays still round-trip
+struct ByteArray<const N: usize>;
+
+impl<'de, const N: usize> Visitor<'de> for ByteArray<N> {
+    type Value = [u8; N];
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} bytes", N)
+    }
+
+    fn visit_bytes<E: Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+        v.try_into().map_err(|_| E::invalid_length(v.len(), &self))
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut out = [0u8; N];
+        for (i, b) in out.iter_mut().enumerate() {
+            *b = seq.next_element()?.ok_or_else(|| A::Error::invalid_length(i, &self))?;
+        }
+        Ok(out)
+    }
+}
+
+fn deserialize_bytes<'de, D: Deserializer<'de>, const N: usize>(d: D) -> Result<[u8; N], D::Error> {
+    d.deserialize_bytes(ByteArray::<N>)
+}
+
+fn serialize_array<S: Serializer>(bytes: &[u8], s: S) -> Result<S::Ok, S::Error> {
+    if s.is_human_readable() {
+        s.serialize_str(&to_hex(bytes))
+    } else {
+        s.serialize_bytes(bytes)
+    }
+}
+
+fn deserialize_array<'de, D: Deserializer<'de>, const N: usize>(d: D) -> Result<[u8; N], D::Error> {
+    if d.is_human_readable() {
+        let s = String::deserialize(d)?;
+        let bytes = hex::decode(s.strip_prefix("0x").unwrap_or(&s)).map_err(D::Error::custom)?;
+        bytes.try_into().map_err(|_| D::Error::custom(format!("expected {} bytes of hex", N)))
+    } else {
+        deserialize_bytes(d)
+    }
+}
+
+/// Byte strings of any length, such as DSTs: hex when human-readable
+pub mod bytes {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(bytes: &[u8], s: S) -> Result<S::Ok, S::Error> {
+        serialize_array(bytes, s)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<Vec<u8>, D::Error> {
+        if d.is_human_readable() {
+            let s = String::deserialize(d)?;
+            hex::decode(s.strip_prefix("0x").unwrap_or(&s)).map_err(D::Error::custom)
+        } else {
+            Vec::<u8>::deserialize(d)
+        }
+    }
+}
+
+pub mod g1_compressed {
+    use super::*;
+    use crate::G1Encoding;
+    use substrate_bn::AffineG1;
+
+    pub fn serialize<S: Serializer>(p: &AffineG1, s: S) -> Result<S::Ok, S::Error> {
+        serialize_array(&p.to_compressed(), s)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<AffineG1, D::Error> {
+        AffineG1::from_compressed(&deserialize_array(d)?).map_err(|e| D::Error::custom(format!("{:?}", e)))
+    }
+}
+
+pub mod g2_compressed {
+    use super::*;
+    use crate::G2Encoding;
+    use substrate_bn::AffineG2;
+
+    pub fn serialize<S: Serializer>(p: &AffineG2, s: S) -> Result<S::Ok, S::Error> {
+        serialize_array(&p.to_compressed(), s)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<AffineG2, D::Error> {
+        AffineG2::from_compressed(&deserialize_array(d)?).map_err(|e| D::Error::custom(format!("{:?}", e)))
+    }
+}
+
+pub mod g1 {
+    use super::*;
+    use crate::G1Encoding;
+    use substrate_bn::AffineG1;
+
+    pub fn serialize<S: Serializer>(p: &AffineG1, s: S) -> Result<S::Ok, S::Error> {
+        let bytes = p.to_uncompressed();
+        if s.is_human_readable() {
+            s.collect_seq([to_hex(&bytes[..32]), to_hex(&bytes[32..])])
+        } else {
+            s.serialize_bytes(&bytes)
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<AffineG1, D::Error> {
+        let bytes: [u8; 64] = if d.is_human_readable() {
+            let [x, y] = <[String; 2]>::deserialize(d)?;
+            [from_hex::<D::Error>(&x)?, from_hex(&y)?].concat().try_into().unwrap()
+        } else {
+            deserialize_bytes(d)?
+        };
+        AffineG1::from_uncompressed(&bytes).map_err(|e| D::Error::custom(format!("{:?}", e)))
+    }
+}
+
+pub mod g2 {
+    use super::*;
+    use crate::G2Encoding;
+    use substrate_bn::AffineG2;
+
+    pub fn serialize<S: Serializer>(p: &AffineG2, s: S) -> Result<S::Ok, S::Error> {
+        let bytes = p.to_uncompressed();
+        if s.is_human_readable() {
+            // The byte encoding puts c1 first (EIP-197); the pairs here read c0, c1
+            let x = [to_hex(&bytes[32..64]), to_hex(&bytes[..32])];
+            let y = [to_hex(&bytes[96..]), to_hex(&bytes[64..96])];
+            s.collect_seq([x, y])
+        } else {
+            s.serialize_bytes(&bytes)
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<AffineG2, D::Error> {
+        let bytes: [u8; 128] = if d.is_human_readable() {
+            let [[x0, x1], [y0, y1]] = <[[String; 2]; 2]>::deserialize(d)?;
+            let limbs: Vec<[u8; 32]> = [x1, x0, y1, y0].iter().map(|s| from_hex::<D::Error>(s)).collect::<Result<_, _>>()?;
+            limbs.concat().try_into().unwrap()
+        } else {
+            deserialize_bytes(d)?
+        };
+        AffineG2::from_uncompressed(&bytes).map_err(|e| D::Error::custom(format!("{:?}", e)))
+    }
+}
+
+pub mod fr {
+    use super::*;
+    use crate::serialize::{fr_from_bytes, fr_to_bytes};
+    use substrate_bn::Fr;
+
+    pub fn serialize<S: Serializer>(x: &Fr, s: S) -> Result<S::Ok, S::Error> {
+        let bytes = fr_to_bytes(*x);
+        if s.is_human_readable() {
+            s.serialize_str(&to_hex(&bytes))
+        } else {
+            s.serialize_bytes(&bytes)
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<Fr, D::Error> {
+        let bytes: [u8; 32] = if d.is_human_readable() {
+            from_hex(&String::deserialize(d)?)?
+        } else {
+            deserialize_bytes(d)?
+        };
+        fr_from_bytes(&bytes).map_err(|e| D::Error::custom(format!("{:?}", e)))
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct FrElement(#[serde(with = "fr")] substrate_bn::Fr);
+
+pub mod fr_vec {
+    use super::*;
+    use substrate_bn::Fr;
+
+    pub fn serialize<S: Serializer>(xs: &[Fr], s: S) -> Result<S::Ok, S::Error> {
+        s.collect_seq(xs.iter().map(|&x| FrElement(x)))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<Vec<Fr>, D::Error> {
+        Ok(Vec::<FrElement>::deserialize(d)?.into_iter().map(|x| x.0).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ::serde::{Deserialize, Serialize};
+    use rand::thread_rng;
+    use substrate_bn::{AffineG1, AffineG2, Fr, Group, G1, G2};
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Input {
+        #[serde(with = "super::g1")]
+        p: AffineG1,
+        #[serde(with = "super::g2")]
+        q: AffineG2,
+        #[serde(with = "super::fr")]
+        s: Fr,
+    }
+
+    fn random_input() -> Input {
+        let mut rng = thread_rng();
+        Input {
+            p: (G1::one() * Fr::random(&mut rng)).into(),
+            q: (G2::one() * Fr::random(&mut rng)).into(),
+            s: Fr::random(&mut rng),
+        }
+    }
+
+    #[test]
+    fn test_json_round_trip() {
+        for _ in 0..4 {
+            let input = random_input();
+            let json = serde_json::to_string(&input).unwrap();
+            assert_eq!(serde_json::from_str::<Input>(&json).unwrap(), input);
+        }
+    }
+
+    #[test]
+    fn test_json_format() {
+        let input = Input { p: AffineG1::one(), q: AffineG2::one(), s: Fr::one() };
+        let json: serde_json::Value = serde_json::to_value(&input).unwrap();
+        let one = format!("0x{:064x}", 1);
+        assert_eq!(json["p"], serde_json::json!([one, format!("0x{:064x}", 2)]));
+        assert_eq!(json["s"], serde_json::json!(one));
+        assert_eq!(json["q"][0][0], "0x1800deef121f1e76426a00665e5c4479674322d4f75edadd46debd5cd992f6ed");
+        assert_eq!(json["q"][0][1], "0x198e9393920d483a7260bfb731fb5d25f1aa493335a9e71297e485b7aef312c2");
+    }
+
+    #[test]
+    fn test_bincode_round_trip() {
+        for _ in 0..4 {
+            let input = random_input();
+            let bytes = bincode::serialize(&input).unwrap();
+            // 64 + 128 + 32 bytes of encoding plus an 8-byte length prefix on each
+            assert_eq!(bytes.len(), 224 + 3 * 8);
+            assert_eq!(bincode::deserialize::<Input>(&bytes).unwrap(), input);
+        }
+    }
+
+    #[test]
+    fn test_rejects_invalid() {
+        let mut json = serde_json::to_value(Input { p: AffineG1::one(), q: AffineG2::one(), s: Fr::one() }).unwrap();
+        json["p"][1] = serde_json::json!(format!("0x{:064x}", 3));
+        assert!(serde_json::from_value::<Input>(json.clone()).is_err());
+
+        // r itself is not a canonical scalar
+        json["p"][1] = serde_json::json!(format!("0x{:064x}", 2));
+        json["s"] = serde_json::json!("0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000001");
+        assert!(serde_json::from_value::<Input>(json).is_err());
+    }
+
+    #[cfg(feature = "std")]
+    mod commitments {
+        use super::*;
+        use crate::{Commitment, CommitmentKey, G1Encoding, Opening};
+
+        const DST: &[u8] = b"QUUX-V01-CS02-with-BN254G1_XMD:SHA-256_SVDW_RO_";
+
+        fn setup() -> (CommitmentKey, Opening, Commitment) {
+            let mut rng = thread_rng();
+            let key = CommitmentKey::new(3, DST).unwrap();
+            let opening = Opening { values: (0..3).map(|_| Fr::random(&mut rng)).collect(), blinding: Fr::random(&mut rng) };
+            let c = Commitment(key.commit(&opening.values, opening.blinding));
+            (key, opening, c)
+        }
+
+        #[test]
+        fn test_commitment_round_trip() {
+            let (_, _, c) = setup();
+            let json = serde_json::to_string(&c).unwrap();
+            assert_eq!(json, format!("\"0x{}\"", hex::encode(c.0.to_compressed())));
+            assert_eq!(serde_json::from_str::<Commitment>(&json).unwrap(), c);
+
+            let bytes = bincode::serialize(&c).unwrap();
+            assert_eq!(bytes.len(), 8 + 32);
+            assert_eq!(bincode::deserialize::<Commitment>(&bytes).unwrap(), c);
+        }
+
+        #[test]
+        fn test_opening_round_trip() {
+            let (key, opening, c) = setup();
+            let json = serde_json::to_string(&opening).unwrap();
+            let decoded: Opening = serde_json::from_str(&json).unwrap();
+            assert!(decoded.values == opening.values && decoded.blinding == opening.blinding);
+
+            let decoded: Opening = bincode::deserialize(&bincode::serialize(&opening).unwrap()).unwrap();
+            assert!(decoded.values == opening.values && decoded.blinding == opening.blinding);
+            assert!(crate::pedersen::verify(&key, &c.0, &decoded));
+        }
+
+        #[test]
+        fn test_key_round_trip() {
+            let (key, opening, c) = setup();
+            let json = serde_json::to_string(&key).unwrap();
+            let generator = hex::encode(AffineG1::from_uncompressed(&key.export()[0]).unwrap().to_compressed());
+            assert_eq!(json, format!("{{\"dst\":\"0x{}\",\"generator\":\"0x{}\",\"len\":3}}", hex::encode(DST), generator));
+
+            let decoded: CommitmentKey = serde_json::from_str(&json).unwrap();
+            assert_eq!(decoded.export(), key.export());
+            let decoded: CommitmentKey = bincode::deserialize(&bincode::serialize(&key).unwrap()).unwrap();
+            assert_eq!(decoded.commit(&opening.values, opening.blinding), c.0);
+
+            let imported = CommitmentKey::from_points(vec![G1::one().into()]).unwrap();
+            assert!(serde_json::to_string(&imported).is_err());
+        }
+
+        #[test]
+        fn test_key_rejects_foreign_generator() {
+            let g: AffineG1 = (G1::one() * Fr::random(&mut thread_rng())).into();
+            let key = CommitmentKey::with_blinding_generator(3, DST, g).unwrap();
+            assert!(serde_json::to_string(&key).is_err());
+
+            // A stored generator that differs from the re-derived one is refused on load
+            let (key, _, _) = setup();
+            let json = serde_json::to_string(&key).unwrap();
+            let derived = hex::encode(AffineG1::from_uncompressed(&key.export()[0]).unwrap().to_compressed());
+            let tampered = json.replace(&derived, &hex::encode(g.to_compressed()));
+            let err = serde_json::from_str::<CommitmentKey>(&tampered).err().unwrap();
+            assert!(err.to_string().contains("not derived from its DST"));
+        }
+
+        #[test]
+        fn test_key_len_bounded() {
+            let (key, _, _) = setup();
+            let json = serde_json::to_string(&key).unwrap().replace("\"len\":3", "\"len\":18446744073709551615");
+            let err = serde_json::from_str::<CommitmentKey>(&json).err().unwrap();
+            assert!(err.to_string().contains("CommitmentKey length"));
+        }
+
+        #[test]
+        fn test_tampered_point_rejected() {
+            let json = serde_json::to_string(&Commitment(AffineG1::one())).unwrap();
+            assert!(json.ends_with("01\""));
+            assert!(serde_json::from_str::<Commitment>(&json).is_ok());
+
+            // x = 0 has no point on the curve, and x = p is not a canonical coordinate
+            let tampered = json.replace("01\"", "00\"");
+            assert!(serde_json::from_str::<Commitment>(&tampered).is_err());
+            let json = "\"0x30644e72e131a029b85045b68181585d97816a916871ca8d3c208c16d87cfd47\"";
+            assert!(serde_json::from_str::<Commitment>(json).is_err());
+        }
+    }
+}
@@ -0,0 +1,521 @@
+use alloc::vec::Vec;
+
+use substrate_bn::{AffineG1, AffineG2, FieldError, Fq, Fq2, Fr, GroupError};
+use subtle::Choice;
+
+use crate::fr::reduce_mod_r;
+use crate::{g1, g2, HashToCurve};
+
+// p < 2^254, so the two most significant bits of a big-endian coordinate are always free
+const SIGN_FLAG: u8 = 0b1000_0000;
+const INFINITY_FLAG: u8 = 0b0100_0000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeserializationError {
+    /// The coordinates do not satisfy the curve equation
+    NotOnCurve,
+    /// The point is on the curve but outside the prime-order subgroup
+    NotInSubgroup,
+    /// A coordinate is not the canonical encoding of an element below p
+    InvalidEncoding,
+    /// The encoding carries the infinity flag; the identity has no affine representation
+    UnexpectedInfinity,
+}
+
+impl From<GroupError> for DeserializationError {
+    fn from(e: GroupError) -> Self {
+        match e {
+            GroupError::NotInSubgroup => DeserializationError::NotInSubgroup,
+            _ => DeserializationError::NotOnCurve,
+        }
+    }
+}
+
+/// Byte encodings of G1 points
+pub trait G1Encoding: Sized {
+    /// Big-endian x with `sgn0(y)` packed into the most significant bit. The next bit is the
+    /// infinity flag, which is reserved and never set, since an affine point is never the identity
+    fn to_compressed(&self) -> [u8; 32];
+    /// Rejects x >= p, x with no point on the curve, and any use of the infinity flag: `0x40`
+    /// followed by zeros is reported as `UnexpectedInfinity`, other uses as `InvalidEncoding`
+    fn from_compressed(bytes: &[u8; 32]) -> Result<Self, DeserializationError>;
+    /// Big-endian x followed by big-endian y, as consumed by the EVM precompiles
+    fn to_uncompressed(&self) -> [u8; 64];
+    /// All-zero input is rejected as off-curve and the explicit infinity flag as `UnexpectedInfinity`
+    fn from_uncompressed(bytes: &[u8; 64]) -> Result<Self, DeserializationError>;
+
+    /// ecAdd/ecMul/ecPairing input (EIP-196). Affine points are never the identity, whose EVM
+    /// encoding is 64 zero bytes
+    fn to_evm_bytes(&self) -> [u8; 64] {
+        self.to_uncompressed()
+    }
+
+    /// Zero bytes are the precompiles' point at infinity and decode to `None`. Anything else needs
+    /// both coordinates below p and on the curve
+    fn from_evm_bytes(bytes: &[u8; 64]) -> Result<Option<Self>, DeserializationError> {
+        if bytes.iter().all(|&b| b == 0) {
+            return Ok(None);
+        }
+        Self::from_uncompressed(bytes).map(Some)
+    }
+}
+
+/// Byte encodings of G2 points. Each `Fq2` coordinate is written imaginary part first, matching
+/// the EVM pairing precompile (EIP-197)
+pub trait G2Encoding: Sized {
+    /// x with `sgn0(y)` packed into the most significant bit and the infinity flag reserved, as in
+    /// `G1Encoding::to_compressed`
+    fn to_compressed(&self) -> [u8; 64];
+    /// Rejects the infinity flag the same way `G1Encoding::from_compressed` does
+    fn from_compressed(bytes: &[u8; 64]) -> Result<Self, DeserializationError>;
+    fn to_uncompressed(&self) -> [u8; 128];
+    fn from_uncompressed(bytes: &[u8; 128]) -> Result<Self, DeserializationError>;
+
+    /// ecPairing input (EIP-197), 128 zero bytes for the identity
+    fn to_evm_bytes(&self) -> [u8; 128] {
+        self.to_uncompressed()
+    }
+
+    /// Zero bytes decode to `None`, like `G1Encoding::from_evm_bytes`
+    fn from_evm_bytes(bytes: &[u8; 128]) -> Result<Option<Self>, DeserializationError> {
+        if bytes.iter().all(|&b| b == 0) {
+            return Ok(None);
+        }
+        Self::from_uncompressed(bytes).map(Some)
+    }
+}
+
+fn fq_to_bytes(x: Fq) -> [u8; 32] {
+    let mut out = [0u8; 32];
+    x.to_big_endian(&mut out).unwrap();
+    out
+}
+
+fn fq_from_bytes(bytes: &[u8]) -> Result<Fq, DeserializationError> {
+    Fq::from_slice(bytes).map_err(|_| DeserializationError::InvalidEncoding)
+}
+
+pub(crate) fn fr_to_bytes(s: Fr) -> [u8; 32] {
+    let mut out = [0u8; 32];
+    s.to_big_endian(&mut out).unwrap();
+    out
+}
+
+pub(crate) fn fr_from_bytes(bytes: &[u8]) -> Result<Fr, DeserializationError> {
+    Fr::from_slice(bytes).map_err(|_| DeserializationError::InvalidEncoding)
+}
+
+fn fq2_to_bytes(x: Fq2) -> [u8; 64] {
+    let mut out = [0u8; 64];
+    out[..32].copy_from_slice(&fq_to_bytes(x.imaginary()));
+    out[32..].copy_from_slice(&fq_to_bytes(x.real()));
+    out
+}
+
+fn fq2_from_bytes(bytes: &[u8]) -> Result<Fq2, DeserializationError> {
+    let c1 = fq_from_bytes(&bytes[..32])?;
+    let c0 = fq_from_bytes(&bytes[32..])?;
+    Ok(Fq2::new(c0, c1))
+}
+
+impl G1Encoding for AffineG1 {
+    fn to_compressed(&self) -> [u8; 32] {
+        let mut out = fq_to_bytes(self.x());
+        out[0] |= (AffineG1::sgn0(self.y()) as u8) << 7;
+        out
+    }
+
+    fn from_compressed(bytes: &[u8; 32]) -> Result<Self, DeserializationError> {
+        if bytes[0] & INFINITY_FLAG != 0 {
+            return Err(if bytes[0] == INFINITY_FLAG && bytes[1..].iter().all(|&b| b == 0) {
+                DeserializationError::UnexpectedInfinity
+            } else {
+                DeserializationError::InvalidEncoding
+            });
+        }
+
+        let sign = bytes[0] >> 7;
+        let mut x_bytes = *bytes;
+        x_bytes[0] &= !SIGN_FLAG;
+
+        let x = fq_from_bytes(&x_bytes)?;
+        let y = (x * x * x + *g1::B).sqrt().ok_or(DeserializationError::NotOnCurve)?;
+
+        let flip = Choice::from(sign ^ AffineG1::sgn0(y) as u8);
+        let y = g1::cmov(y, -y, flip);
+
+        Ok(AffineG1::new(x, y)?)
+    }
+
+    fn to_uncompressed(&self) -> [u8; 64] {
+        let mut out = [0u8; 64];
+        out[..32].copy_from_slice(&fq_to_bytes(self.x()));
+        out[32..].copy_from_slice(&fq_to_bytes(self.y()));
+        out
+    }
+
+    fn from_uncompressed(bytes: &[u8; 64]) -> Result<Self, DeserializationError> {
+        if bytes[0] == INFINITY_FLAG && bytes[1..].iter().all(|&b| b == 0) {
+            return Err(DeserializationError::UnexpectedInfinity);
+        }
+
+        let x = fq_from_bytes(&bytes[..32])?;
+        let y = fq_from_bytes(&bytes[32..])?;
+
+        Ok(AffineG1::new(x, y)?)
+    }
+}
+
+impl G2Encoding for AffineG2 {
+    fn to_compressed(&self) -> [u8; 64] {
+        let mut out = fq2_to_bytes(self.x());
+        out[0] |= (AffineG2::sgn0(self.y()) as u8) << 7;
+        out
+    }
+
+    fn from_compressed(bytes: &[u8; 64]) -> Result<Self, DeserializationError> {
+        if bytes[0] & INFINITY_FLAG != 0 {
+            return Err(if bytes[0] == INFINITY_FLAG && bytes[1..].iter().all(|&b| b == 0) {
+                DeserializationError::UnexpectedInfinity
+            } else {
+                DeserializationError::InvalidEncoding
+            });
+        }
+
+        let sign = bytes[0] >> 7;
+        let mut x_bytes = *bytes;
+        x_bytes[0] &= !SIGN_FLAG;
+
+        let x = fq2_from_bytes(&x_bytes)?;
+        let y = (x * x * x + *g2::B).sqrt().ok_or(DeserializationError::NotOnCurve)?;
+
+        let flip = Choice::from(sign ^ AffineG2::sgn0(y) as u8);
+        let y = g2::cmov(y, Fq2::zero() - y, flip);
+
+        Ok(AffineG2::new(x, y)?)
+    }
+
+    fn to_uncompressed(&self) -> [u8; 128] {
+        let mut out = [0u8; 128];
+        out[..64].copy_from_slice(&fq2_to_bytes(self.x()));
+        out[64..].copy_from_slice(&fq2_to_bytes(self.y()));
+        out
+    }
+
+    fn from_uncompressed(bytes: &[u8; 128]) -> Result<Self, DeserializationError> {
+        if bytes[0] == INFINITY_FLAG && bytes[1..].iter().all(|&b| b == 0) {
+            return Err(DeserializationError::UnexpectedInfinity);
+        }
+
+        let x = fq2_from_bytes(&bytes[..64])?;
+        let y = fq2_from_bytes(&bytes[64..])?;
+
+        Ok(AffineG2::new(x, y)?)
+    }
+}
+
+// `TryFrom<[u8; N]> for AffineG1` would implement a foreign trait for foreign types, so the
+// conversions go through these byte wrappers instead
+macro_rules! encoding {
+    ($name:ident, $point:ident, $len:expr, $to:ident, $from:ident) => {
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        pub struct $name(pub [u8; $len]);
+
+        impl From<$point> for $name {
+            fn from(p: $point) -> Self {
+                $name(p.$to())
+            }
+        }
+
+        impl TryFrom<$name> for $point {
+            type Error = DeserializationError;
+
+            fn try_from(bytes: $name) -> Result<Self, Self::Error> {
+                $point::$from(&bytes.0)
+            }
+        }
+    };
+}
+
+encoding!(CompressedG1, AffineG1, 32, to_compressed, from_compressed);
+encoding!(UncompressedG1, AffineG1, 64, to_uncompressed, from_uncompressed);
+encoding!(CompressedG2, AffineG2, 64, to_compressed, from_compressed);
+encoding!(UncompressedG2, AffineG2, 128, to_uncompressed, from_uncompressed);
+
+/// Little-endian counterparts of the big-endian field encodings, for libraries and contracts that
+/// store elements least significant byte first
+pub trait LeBytes: Sized {
+    /// Reads `bytes` as a little-endian integer of any length and reduces it mod the field order
+    fn from_le_bytes_mod_order(bytes: &[u8]) -> Result<Self, FieldError>;
+    fn to_le_bytes(self) -> [u8; 32];
+}
+
+impl LeBytes for Fq {
+    fn from_le_bytes_mod_order(bytes: &[u8]) -> Result<Self, FieldError> {
+        let be: Vec<u8> = bytes.iter().rev().copied().collect();
+        Fq::from_be_bytes_mod_order(&be)
+    }
+
+    fn to_le_bytes(self) -> [u8; 32] {
+        let mut out = fq_to_bytes(self);
+        out.reverse();
+        out
+    }
+}
+
+// Reduction against r can't fail; the `Result` keeps the signature the same as for `Fq`
+impl LeBytes for Fr {
+    fn from_le_bytes_mod_order(bytes: &[u8]) -> Result<Self, FieldError> {
+        let be: Vec<u8> = bytes.iter().rev().copied().collect();
+        Ok(reduce_mod_r(&be))
+    }
+
+    fn to_le_bytes(self) -> [u8; 32] {
+        let mut out = fr_to_bytes(self);
+        out.reverse();
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::thread_rng;
+    use substrate_bn::{Fr, Group, G1, G2};
+
+    #[test]
+    fn test_compressed_generator() {
+        // x = 1, y = 2 is even, so the sign bit stays clear
+        let mut expected = [0u8; 32];
+        expected[31] = 1;
+        assert_eq!(AffineG1::one().to_compressed(), expected);
+
+        // -G has the odd root and sets it
+        let neg: AffineG1 = AffineG1::from_jacobian(-G1::one()).unwrap();
+        expected[0] = SIGN_FLAG;
+        assert_eq!(neg.to_compressed(), expected);
+        assert!(AffineG1::from_compressed(&expected).unwrap() == neg);
+    }
+
+    #[test]
+    fn test_compressed_round_trip() {
+        let mut rng = thread_rng();
+        for _ in 0..16 {
+            let p = AffineG1::from_jacobian(G1::one() * Fr::random(&mut rng)).unwrap();
+            assert!(AffineG1::from_compressed(&p.to_compressed()).unwrap() == p);
+        }
+
+        let p = AffineG1::hash(b"abc", b"QUUX-V01-CS02-with-BN254G1_XMD:SHA-256_SVDW_RO_");
+        assert!(AffineG1::from_compressed(&p.to_compressed()).unwrap() == p);
+    }
+
+    #[test]
+    fn test_compressed_rejects_invalid() {
+        // x = p is not canonical
+        let p = hex::decode("30644e72e131a029b85045b68181585d97816a916871ca8d3c208c16d87cfd47").unwrap();
+        let bytes: [u8; 32] = p.try_into().unwrap();
+        assert_eq!(AffineG1::from_compressed(&bytes), Err(DeserializationError::InvalidEncoding));
+
+        // x = 0 gives x³ + 3 = 3, which is not a square
+        assert_eq!(AffineG1::from_compressed(&[0u8; 32]), Err(DeserializationError::NotOnCurve));
+    }
+
+    #[test]
+    fn test_compressed_infinity() {
+        let mut bytes = [0u8; 32];
+        bytes[0] = INFINITY_FLAG;
+        assert_eq!(AffineG1::from_compressed(&bytes), Err(DeserializationError::UnexpectedInfinity));
+
+        // The flag with a sign bit or leftover x bits is not a valid identity
+        bytes[0] |= SIGN_FLAG;
+        assert_eq!(AffineG1::from_compressed(&bytes), Err(DeserializationError::InvalidEncoding));
+        let mut bytes = AffineG1::one().to_compressed();
+        bytes[0] |= INFINITY_FLAG;
+        assert_eq!(AffineG1::from_compressed(&bytes), Err(DeserializationError::InvalidEncoding));
+    }
+
+    #[test]
+    fn test_compressed_sign_bit_selects_root() {
+        let mut rng = thread_rng();
+        for _ in 0..8 {
+            let msg: [u8; 32] = rand::Rng::gen(&mut rng);
+            let p = AffineG1::hash(&msg, b"QUUX-V01-CS02-with-BN254G1_XMD:SHA-256_SVDW_RO_");
+            let mut bytes = p.to_compressed();
+            bytes[0] ^= SIGN_FLAG;
+            let q = AffineG1::from_compressed(&bytes).unwrap();
+            assert!(q.x() == p.x() && q.y() == -p.y());
+        }
+    }
+
+    #[test]
+    fn test_uncompressed_round_trip() {
+        let mut expected = [0u8; 64];
+        expected[31] = 1;
+        expected[63] = 2;
+        assert_eq!(AffineG1::one().to_uncompressed(), expected);
+        assert!(AffineG1::from_uncompressed(&expected).unwrap() == AffineG1::one());
+
+        let mut rng = thread_rng();
+        for _ in 0..16 {
+            let p = AffineG1::from_jacobian(G1::one() * Fr::random(&mut rng)).unwrap();
+            assert!(AffineG1::from_uncompressed(&p.to_uncompressed()).unwrap() == p);
+        }
+    }
+
+    #[test]
+    fn test_uncompressed_rejects_invalid() {
+        // (1, 3) is off the curve
+        let mut bytes = [0u8; 64];
+        bytes[31] = 1;
+        bytes[63] = 3;
+        assert_eq!(AffineG1::from_uncompressed(&bytes), Err(DeserializationError::NotOnCurve));
+
+        // The precompiles' all-zero identity needs the explicit flag here
+        assert_eq!(AffineG1::from_uncompressed(&[0u8; 64]), Err(DeserializationError::NotOnCurve));
+        let mut bytes = [0u8; 64];
+        bytes[0] = INFINITY_FLAG;
+        assert_eq!(AffineG1::from_uncompressed(&bytes), Err(DeserializationError::UnexpectedInfinity));
+    }
+
+    // The generator as the EVM precompiles expect it: x.c1 || x.c0 || y.c1 || y.c0
+    const G2_GENERATOR: &str = "198e9393920d483a7260bfb731fb5d25f1aa493335a9e71297e485b7aef312c21800deef121f1e76426a00665e5c4479674322d4f75edadd46debd5cd992f6ed090689d0585ff075ec9e99ad690c3395bc4b313370b38ef355acdadcd122975b12c85ea5db8c6deb4aab71808dcb408fe3d1e7690c43d37b4ce6cc0166fa7daa";
+
+    #[test]
+    fn test_g2_uncompressed_evm_format() {
+        let bytes: [u8; 128] = hex::decode(G2_GENERATOR).unwrap().try_into().unwrap();
+        assert_eq!(AffineG2::one().to_uncompressed(), bytes);
+        assert!(AffineG2::from_uncompressed(&bytes).unwrap() == AffineG2::one());
+    }
+
+    #[test]
+    fn test_g2_round_trip() {
+        let mut rng = thread_rng();
+        for _ in 0..8 {
+            let p = AffineG2::from_jacobian(G2::one() * Fr::random(&mut rng)).unwrap();
+            assert!(AffineG2::from_compressed(&p.to_compressed()).unwrap() == p);
+            assert!(AffineG2::from_uncompressed(&p.to_uncompressed()).unwrap() == p);
+        }
+
+        // The generator's y has sgn0 = 0, so its compressed form is just x
+        let bytes: [u8; 128] = hex::decode(G2_GENERATOR).unwrap().try_into().unwrap();
+        assert_eq!(AffineG2::one().to_compressed()[..], bytes[..64]);
+    }
+
+    #[test]
+    fn test_g2_rejects_invalid() {
+        let mut bytes: [u8; 128] = hex::decode(G2_GENERATOR).unwrap().try_into().unwrap();
+        bytes[127] ^= 1;
+        assert_eq!(AffineG2::from_uncompressed(&bytes), Err(DeserializationError::NotOnCurve));
+        assert_eq!(AffineG2::from_uncompressed(&[0u8; 128]), Err(DeserializationError::NotOnCurve));
+    }
+
+    #[test]
+    fn test_g2_compressed_infinity() {
+        let mut bytes = [0u8; 64];
+        bytes[0] = INFINITY_FLAG;
+        assert_eq!(AffineG2::from_compressed(&bytes), Err(DeserializationError::UnexpectedInfinity));
+
+        bytes[0] |= SIGN_FLAG;
+        assert_eq!(AffineG2::from_compressed(&bytes), Err(DeserializationError::InvalidEncoding));
+        let mut bytes = AffineG2::one().to_compressed();
+        bytes[0] |= INFINITY_FLAG;
+        assert_eq!(AffineG2::from_compressed(&bytes), Err(DeserializationError::InvalidEncoding));
+
+        // Neither encoding ever sets the flag
+        assert_eq!(AffineG1::one().to_compressed()[0] & INFINITY_FLAG, 0);
+        assert_eq!(AffineG2::one().to_compressed()[0] & INFINITY_FLAG, 0);
+    }
+
+    #[test]
+    fn test_evm_bytes() {
+        // 2G, the ecAdd result for (1, 2) + (1, 2) from the EIP-196 test cases
+        let two = "030644e72e131a029b85045b68181585d97816a916871ca8d3c208c16d87cfd315ed738c0e0a7c92e7845f96b2ae9c0a68a6a449e3538fc7ff3ebf7a5a18a2c4";
+        let p = AffineG1::from_jacobian(G1::one() + G1::one()).unwrap();
+        assert_eq!(hex::encode(p.to_evm_bytes()), two);
+        assert!(AffineG1::from_evm_bytes(&p.to_evm_bytes()).unwrap().unwrap() == p);
+
+        let bytes: [u8; 128] = hex::decode(G2_GENERATOR).unwrap().try_into().unwrap();
+        assert_eq!(AffineG2::one().to_evm_bytes(), bytes);
+        assert!(AffineG2::from_evm_bytes(&bytes).unwrap().unwrap() == AffineG2::one());
+
+        let mut rng = thread_rng();
+        for _ in 0..4 {
+            let p = AffineG1::from_jacobian(G1::one() * Fr::random(&mut rng)).unwrap();
+            assert!(AffineG1::from_evm_bytes(&p.to_evm_bytes()).unwrap().unwrap() == p);
+            let q = AffineG2::from_jacobian(G2::one() * Fr::random(&mut rng)).unwrap();
+            assert!(AffineG2::from_evm_bytes(&q.to_evm_bytes()).unwrap().unwrap() == q);
+        }
+    }
+
+    #[test]
+    fn test_evm_bytes_infinity_and_invalid() {
+        assert!(AffineG1::from_evm_bytes(&[0u8; 64]).unwrap().is_none());
+        assert!(AffineG2::from_evm_bytes(&[0u8; 128]).unwrap().is_none());
+
+        // y = p + 2 is out of range even though it reduces to the valid y = 2
+        let mut bytes = AffineG1::one().to_evm_bytes();
+        bytes[32..].copy_from_slice(&hex::decode("30644e72e131a029b85045b68181585d97816a916871ca8d3c208c16d87cfd49").unwrap());
+        assert_eq!(AffineG1::from_evm_bytes(&bytes).err(), Some(DeserializationError::InvalidEncoding));
+
+        let mut bytes = AffineG1::one().to_evm_bytes();
+        bytes[63] = 3;
+        assert_eq!(AffineG1::from_evm_bytes(&bytes).err(), Some(DeserializationError::NotOnCurve));
+
+        let mut bytes: [u8; 128] = hex::decode(G2_GENERATOR).unwrap().try_into().unwrap();
+        bytes[0] ^= 0x01;
+        assert!(AffineG2::from_evm_bytes(&bytes).is_err());
+    }
+
+    #[test]
+    fn test_try_from_wrappers() {
+        let p = AffineG1::hash(b"abc", b"QUUX-V01-CS02-with-BN254G1_XMD:SHA-256_SVDW_RO_");
+        assert!(AffineG1::try_from(CompressedG1::from(p)).unwrap() == p);
+        assert!(AffineG1::try_from(UncompressedG1::from(p)).unwrap() == p);
+        assert_eq!(UncompressedG1::from(p).0, p.to_uncompressed());
+
+        let q = AffineG2::hash(b"abc", b"QUUX-V01-CS02-with-BN254G2_XMD:SHA-256_SVDW_RO_");
+        assert!(AffineG2::try_from(CompressedG2::from(q)).unwrap() == q);
+        assert!(AffineG2::try_from(UncompressedG2::from(q)).unwrap() == q);
+
+        let mut bytes = [0u8; 64];
+        bytes[0] = INFINITY_FLAG;
+        assert_eq!(AffineG1::try_from(UncompressedG1(bytes)), Err(DeserializationError::UnexpectedInfinity));
+    }
+
+    #[test]
+    fn test_g2_rejects_non_subgroup() {
+        // SVDW output before cofactor clearing is on the twist but almost never in the r-torsion
+        let u = Fq2::new(Fq::one(), Fq::one());
+        let q = AffineG2::map_to_curve(u).unwrap();
+        let mut bytes = [0u8; 128];
+        bytes[..64].copy_from_slice(&fq2_to_bytes(q.x()));
+        bytes[64..].copy_from_slice(&fq2_to_bytes(q.y()));
+        assert_eq!(AffineG2::from_uncompressed(&bytes), Err(DeserializationError::NotInSubgroup));
+    }
+
+    #[test]
+    fn test_le_bytes() {
+        let mut rng = thread_rng();
+        let x = Fq::random(&mut rng);
+        let mut be = [0u8; 32];
+        x.to_big_endian(&mut be).unwrap();
+        let mut le = be;
+        le.reverse();
+        assert_eq!(x.to_le_bytes(), le);
+        assert!(Fq::from_le_bytes_mod_order(&le).unwrap() == x);
+        assert!(Fq::from_le_bytes_mod_order(&[3]).unwrap() == Fq::from_str("3").unwrap());
+
+        // Wider than the field: reduces the same as the big-endian reading of the reversed bytes
+        let wide: Vec<u8> = (0..48u8).collect();
+        let wide_be: Vec<u8> = wide.iter().rev().copied().collect();
+        assert!(Fq::from_le_bytes_mod_order(&wide).unwrap() == Fq::from_be_bytes_mod_order(&wide_be).unwrap());
+
+        let s = Fr::random(&mut rng);
+        assert!(Fr::from_le_bytes_mod_order(&s.to_le_bytes()).unwrap() == s);
+        // r + 1, little-endian
+        let mut r_plus_one = hex::decode("30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000002").unwrap();
+        r_plus_one.reverse();
+        assert!(Fr::from_le_bytes_mod_order(&r_plus_one).unwrap() == Fr::one());
+        assert!(Fr::from_le_bytes_mod_order(&[]).unwrap() == Fr::zero());
+    }
+}
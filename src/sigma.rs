@@ -0,0 +1,309 @@
+use rand_core::RngCore;
+use substrate_bn::{AffineG1, Fr, G1};
+
+use crate::pedersen::value_generator;
+use crate::serialize::{fr_from_bytes, fr_to_bytes};
+use crate::{CommitmentKey, CommitmentParams, DeserializationError, G1Encoding, Transcript};
+
+/// Non-interactive proof of knowledge of an opening (v, r) of C = r·G + Σ v_i·H_i
+#[derive(Clone, Debug, PartialEq)]
+pub struct OpeningProof {
+    /// Commitment to the prover's nonces
+    pub a: AffineG1,
+    /// z_i = k_i + e·v_i
+    pub responses: Vec<Fr>,
+    /// z_r = k_r + e·r
+    pub blinding_response: Fr,
+}
+
+// Binds the key through its fingerprint, the statement C, the first message A and the vector length
+fn opening_challenge(key: &CommitmentKey, commitment: AffineG1, a: AffineG1, n: usize, transcript: &mut Transcript) -> Fr {
+    transcript.absorb_bytes(b"key", &key.fingerprint());
+    transcript.absorb_point_g1(b"c", commitment);
+    transcript.absorb_point_g1(b"a", a);
+    transcript.absorb_bytes(b"n", &(n as u64).to_be_bytes());
+    transcript.squeeze_challenge(b"opening")
+}
+
+/// Panics if `values` is longer than the key
+pub fn prove_opening(
+    key: &CommitmentKey,
+    values: &[Fr],
+    blinding: Fr,
+    transcript: &mut Transcript,
+    rng: &mut impl RngCore,
+) -> OpeningProof {
+    let commitment = key.commit(values, blinding);
+
+    let ks: Vec<Fr> = values.iter().map(|_| Fr::random(rng)).collect();
+    let k_r = Fr::random(rng);
+    let a = key.commit(&ks, k_r);
+
+    let e = opening_challenge(key, commitment, a, values.len(), transcript);
+    OpeningProof {
+        a,
+        responses: ks.iter().zip(values).map(|(&k, &v)| k + e * v).collect(),
+        blinding_response: k_r + e * blinding,
+    }
+}
+
+// Σ z_i·H_i + z_r·G == A + e·C, with e squeezed from the verifier's own transcript
+pub fn verify_opening(key: &CommitmentKey, commitment: &AffineG1, proof: &OpeningProof, transcript: &mut Transcript) -> bool {
+    if proof.responses.len() > key.len() {
+        return false;
+    }
+    let e = opening_challenge(key, *commitment, proof.a, proof.responses.len(), transcript);
+
+    let lhs = G1::from(key.commit(&proof.responses, proof.blinding_response));
+    lhs == G1::from(proof.a) + G1::from(*commitment) * e
+}
+
+fn read<'a>(bytes: &mut &'a [u8], n: usize) -> Result<&'a [u8], DeserializationError> {
+    if bytes.len() < n {
+        return Err(DeserializationError::InvalidEncoding);
+    }
+    let (head, tail) = bytes.split_at(n);
+    *bytes = tail;
+    Ok(head)
+}
+
+impl OpeningProof {
+    /// `compressed A || n || z_1..z_n || z_r`, n as a big-endian u32
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(32 + 4 + 32 * (self.responses.len() + 1));
+        out.extend(self.a.to_compressed());
+        out.extend((self.responses.len() as u32).to_be_bytes());
+        for &z in &self.responses {
+            out.extend(fr_to_bytes(z));
+        }
+        out.extend(fr_to_bytes(self.blinding_response));
+        out
+    }
+
+    pub fn from_bytes(mut bytes: &[u8]) -> Result<Self, DeserializationError> {
+        let a = AffineG1::from_compressed(read(&mut bytes, 32)?.try_into().unwrap())?;
+        let n = u32::from_be_bytes(read(&mut bytes, 4)?.try_into().unwrap()) as usize;
+        let responses = (0..n).map(|_| fr_from_bytes(read(&mut bytes, 32)?)).collect::<Result<Vec<_>, _>>()?;
+        let blinding_response = fr_from_bytes(read(&mut bytes, 32)?)?;
+
+        if !bytes.is_empty() {
+            return Err(DeserializationError::InvalidEncoding);
+        }
+        Ok(OpeningProof { a, responses, blinding_response })
+    }
+}
+
+/// Proof that C1 = v·H_0 + r1·H and C2 = v·H_1 + r2·H hide the same v, where H_i are the value
+/// generators of the params and H their blinding generator. Two nonce commitments and a response
+/// per secret (v, r1, r2)
+#[derive(Clone, Debug, PartialEq)]
+pub struct EqualityProof {
+    pub a1: AffineG1,
+    pub a2: AffineG1,
+    pub value_response: Fr,
+    pub blinding_responses: [Fr; 2],
+}
+
+// Binds the params the generators come from, both statements and both first messages
+fn equality_challenge(
+    params: &CommitmentParams,
+    c1: AffineG1,
+    c2: AffineG1,
+    a1: AffineG1,
+    a2: AffineG1,
+    transcript: &mut Transcript,
+) -> Fr {
+    transcript.absorb_bytes(b"dst", &params.dst);
+    transcript.absorb_point_g1(b"h", params.generator);
+    transcript.absorb_point_g1(b"c1", c1);
+    transcript.absorb_point_g1(b"c2", c2);
+    transcript.absorb_point_g1(b"a1", a1);
+    transcript.absorb_point_g1(b"a2", a2);
+    transcript.squeeze_challenge(b"equality")
+}
+
+pub fn prove_equality(
+    v: Fr,
+    r1: Fr,
+    r2: Fr,
+    c1: AffineG1,
+    c2: AffineG1,
+    params: &CommitmentParams,
+    transcript: &mut Transcript,
+    rng: &mut impl RngCore,
+) -> EqualityProof {
+    let (g1, g2, h) = (value_generator(0, &params.dst), value_generator(1, &params.dst), params.generator);
+
+    let (k_v, k1, k2) = (Fr::random(rng), Fr::random(rng), Fr::random(rng));
+    // The shared nonce k_v is what ties the two openings to one value
+    let a1: AffineG1 = (G1::from(g1) * k_v + G1::from(h) * k1).into();
+    let a2: AffineG1 = (G1::from(g2) * k_v + G1::from(h) * k2).into();
+
+    let e = equality_challenge(params, c1, c2, a1, a2, transcript);
+    EqualityProof { a1, a2, value_response: k_v + e * v, blinding_responses: [k1 + e * r1, k2 + e * r2] }
+}
+
+// z_v·H_0 + z_1·H == A1 + e·C1 and z_v·H_1 + z_2·H == A2 + e·C2
+pub fn verify_equality(
+    c1: AffineG1,
+    c2: AffineG1,
+    proof: &EqualityProof,
+    params: &CommitmentParams,
+    transcript: &mut Transcript,
+) -> bool {
+    let g1 = G1::from(value_generator(0, &params.dst));
+    let g2 = G1::from(value_generator(1, &params.dst));
+    let h = G1::from(params.generator);
+    let e = equality_challenge(params, c1, c2, proof.a1, proof.a2, transcript);
+    let [z1, z2] = proof.blinding_responses;
+
+    g1 * proof.value_response + h * z1 == G1::from(proof.a1) + G1::from(c1) * e
+        && g2 * proof.value_response + h * z2 == G1::from(proof.a2) + G1::from(c2) * e
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::{thread_rng, SeedableRng};
+    use substrate_bn::Group;
+
+    use crate::pedersen::blinding_generator;
+
+    const KEY_DST: &[u8] = b"QUUX-V01-CS02-with-BN254G1_XMD:SHA-256_SVDW_RO_";
+    const DST: &[u8] = b"OPENING-POK-V01";
+
+    fn setup() -> (CommitmentKey, Vec<Fr>, Fr, AffineG1) {
+        let mut rng = thread_rng();
+        let key = CommitmentKey::new(4, KEY_DST).unwrap();
+        let values = (0..4).map(|_| Fr::random(&mut rng)).collect::<Vec<_>>();
+        let blinding = Fr::random(&mut rng);
+        let c = key.commit(&values, blinding);
+        (key, values, blinding, c)
+    }
+
+    fn prove(key: &CommitmentKey, values: &[Fr], blinding: Fr, dst: &[u8]) -> OpeningProof {
+        prove_opening(key, values, blinding, &mut Transcript::new(dst), &mut thread_rng())
+    }
+
+    fn verify(key: &CommitmentKey, c: &AffineG1, proof: &OpeningProof, dst: &[u8]) -> bool {
+        verify_opening(key, c, proof, &mut Transcript::new(dst))
+    }
+
+    #[test]
+    fn test_prove_verify_opening() {
+        let (key, values, blinding, c) = setup();
+        let proof = prove(&key, &values, blinding, DST);
+        assert!(verify(&key, &c, &proof, DST));
+
+        // Wrong witness cannot produce an accepting proof for c
+        let proof = prove(&key, &values, blinding + Fr::one(), DST);
+        assert!(!verify(&key, &c, &proof, DST));
+    }
+
+    #[test]
+    fn test_verify_opening_rejects_tampering() {
+        let (key, values, blinding, c) = setup();
+        let proof = prove(&key, &values, blinding, DST);
+
+        let other = key.commit(&values, blinding + Fr::one());
+        assert!(!verify(&key, &other, &proof, DST));
+
+        let mut tampered = proof.clone();
+        tampered.responses[0] = tampered.responses[0] + Fr::one();
+        assert!(!verify(&key, &c, &tampered, DST));
+
+        let mut tampered = proof.clone();
+        tampered.blinding_response = tampered.blinding_response + Fr::one();
+        assert!(!verify(&key, &c, &tampered, DST));
+    }
+
+    #[test]
+    fn test_challenge_domain_separation() {
+        let (key, values, blinding, c) = setup();
+        let a = prove(&key, &values, blinding, b"DST-A");
+        let b = prove(&key, &values, blinding, b"DST-B");
+        assert!(verify(&key, &c, &a, b"DST-A"));
+        assert!(verify(&key, &c, &b, b"DST-B"));
+
+        // Each proof only verifies under the transcript the verifier expects
+        assert!(!verify(&key, &c, &a, b"DST-B"));
+        assert!(!verify(&key, &c, &b, b"DST-A"));
+    }
+
+    #[test]
+    fn test_opening_bound_to_key() {
+        let (key, values, blinding, _) = setup();
+        let h = G1::from(blinding_generator(KEY_DST)) + G1::one();
+        let other = CommitmentKey::with_blinding_generator(4, KEY_DST, h.into()).unwrap();
+        assert!(key.fingerprint() != other.fingerprint());
+
+        // Same generators for the values, so the proof's equation alone can't tell the keys apart
+        let c = other.commit(&values, blinding);
+        let proof = prove(&other, &values, blinding, DST);
+        assert!(verify(&other, &c, &proof, DST));
+        assert!(!verify(&key, &c, &proof, DST));
+    }
+
+    #[test]
+    fn test_prove_opening_uses_caller_rng() {
+        let (key, values, blinding, c) = setup();
+        let prove_seeded = |seed| prove_opening(&key, &values, blinding, &mut Transcript::new(DST), &mut StdRng::seed_from_u64(seed));
+        assert_eq!(prove_seeded(7), prove_seeded(7));
+        assert!(prove_seeded(7) != prove_seeded(8));
+        assert!(verify(&key, &c, &prove_seeded(7), DST));
+    }
+
+    #[test]
+    fn test_opening_proof_bytes() {
+        let (key, values, blinding, c) = setup();
+        let proof = prove(&key, &values, blinding, DST);
+
+        let bytes = proof.to_bytes();
+        assert_eq!(bytes.len(), 32 + 4 + 32 * 5);
+        let decoded = OpeningProof::from_bytes(&bytes).unwrap();
+        assert_eq!(decoded, proof);
+        assert!(verify(&key, &c, &decoded, DST));
+
+        assert_eq!(OpeningProof::from_bytes(&bytes[..bytes.len() - 1]), Err(DeserializationError::InvalidEncoding));
+        assert_eq!(OpeningProof::from_bytes(&[bytes.clone(), vec![0]].concat()), Err(DeserializationError::InvalidEncoding));
+    }
+
+    fn equality_setup(v1: Fr, v2: Fr) -> (CommitmentParams, Fr, Fr, AffineG1, AffineG1) {
+        let mut rng = thread_rng();
+        let params = CommitmentParams::new(KEY_DST).unwrap();
+        let (r1, r2) = (Fr::random(&mut rng), Fr::random(&mut rng));
+        let c1 = crate::commit([v1], &params, r1);
+        let c2 = crate::commit([Fr::zero(), v2], &params, r2);
+        (params, r1, r2, c1, c2)
+    }
+
+    #[test]
+    fn test_prove_verify_equality() {
+        let v = Fr::random(&mut thread_rng());
+        let (params, r1, r2, c1, c2) = equality_setup(v, v);
+
+        let proof = prove_equality(v, r1, r2, c1, c2, &params, &mut Transcript::new(b"eq"), &mut thread_rng());
+        assert!(verify_equality(c1, c2, &proof, &params, &mut Transcript::new(b"eq")));
+
+        // Bound to the transcript it was made under
+        assert!(!verify_equality(c1, c2, &proof, &params, &mut Transcript::new(b"other")));
+        assert!(!verify_equality(c2, c1, &proof, &params, &mut Transcript::new(b"eq")));
+
+        // and to the params the generators were derived from
+        let other = CommitmentParams::new(b"OTHER-DST").unwrap();
+        assert!(!verify_equality(c1, c2, &proof, &other, &mut Transcript::new(b"eq")));
+    }
+
+    #[test]
+    fn test_verify_equality_rejects_mismatched_values() {
+        let mut rng = thread_rng();
+        let v = Fr::random(&mut rng);
+        let (params, r1, r2, c1, c2) = equality_setup(v, v + Fr::one());
+
+        for claimed in [v, v + Fr::one()] {
+            let proof = prove_equality(claimed, r1, r2, c1, c2, &params, &mut Transcript::new(b"eq"), &mut rng);
+            assert!(!verify_equality(c1, c2, &proof, &params, &mut Transcript::new(b"eq")));
+        }
+    }
+}
@@ -0,0 +1,98 @@
+use sha2::digest::Digest;
+use substrate_bn::{AffineG1, Fr};
+
+use crate::expand::Sha256;
+use crate::serialize::fr_to_bytes;
+use crate::{hash_to_fr, G1Encoding};
+
+const CHALLENGE_DST_PREFIX: &[u8] = b"TRANSCRIPT-CHALLENGE-V01-";
+
+/// Running Fiat-Shamir transcript. Every absorb is length-prefixed and labelled, so the state
+/// depends on the exact sequence of (label, data) pairs and not just their concatenation
+#[derive(Clone)]
+pub struct Transcript {
+    state: Sha256,
+}
+
+impl Transcript {
+    /// `protocol` separates transcripts of unrelated proof systems
+    pub fn new(protocol: &[u8]) -> Self {
+        let mut t = Transcript { state: Sha256::new() };
+        t.absorb_bytes(b"protocol", protocol);
+        t
+    }
+
+    pub fn absorb_bytes(&mut self, label: &[u8], data: &[u8]) {
+        self.state.update((label.len() as u64).to_be_bytes());
+        self.state.update(label);
+        self.state.update((data.len() as u64).to_be_bytes());
+        self.state.update(data);
+    }
+
+    pub fn absorb_point_g1(&mut self, label: &[u8], p: AffineG1) {
+        self.absorb_bytes(label, &p.to_uncompressed());
+    }
+
+    pub fn absorb_scalar(&mut self, label: &[u8], s: Fr) {
+        self.absorb_bytes(label, &fr_to_bytes(s));
+    }
+
+    /// hash_to_field over the current state under a DST derived from `label`. The challenge is fed
+    /// back in, so consecutive squeezes give independent values
+    pub fn squeeze_challenge(&mut self, label: &[u8]) -> Fr {
+        let digest = self.state.clone().finalize();
+        let dst = [CHALLENGE_DST_PREFIX, label].concat();
+        let e = hash_to_fr(&digest, &dst);
+        self.absorb_scalar(label, e);
+        e
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use substrate_bn::{Group, G1};
+
+    #[test]
+    fn test_transcript_deterministic() {
+        let run = || {
+            let mut t = Transcript::new(b"test");
+            t.absorb_point_g1(b"C", G1::one().into());
+            t.absorb_scalar(b"s", Fr::one());
+            t.squeeze_challenge(b"e")
+        };
+        assert!(run() == run());
+    }
+
+    #[test]
+    fn test_transcript_order_dependent() {
+        let mut a = Transcript::new(b"test");
+        a.absorb_bytes(b"x", b"1");
+        a.absorb_bytes(b"y", b"2");
+
+        let mut b = Transcript::new(b"test");
+        b.absorb_bytes(b"y", b"2");
+        b.absorb_bytes(b"x", b"1");
+        assert!(a.clone().squeeze_challenge(b"e") != b.squeeze_challenge(b"e"));
+
+        // Moving bytes between label and data changes the state thanks to the length prefixes
+        let mut c = Transcript::new(b"test");
+        c.absorb_bytes(b"x1", b"");
+        c.absorb_bytes(b"y", b"2");
+        assert!(a.clone().squeeze_challenge(b"e") != c.squeeze_challenge(b"e"));
+
+        assert!(a.clone().squeeze_challenge(b"e") != Transcript::new(b"other").squeeze_challenge(b"e"));
+    }
+
+    #[test]
+    fn test_squeeze_separation() {
+        let mut t = Transcript::new(b"test");
+        let e1 = t.squeeze_challenge(b"e");
+        let e2 = t.squeeze_challenge(b"e");
+        assert!(e1 != e2);
+
+        // Same state, different label
+        let t = Transcript::new(b"test");
+        assert!(t.clone().squeeze_challenge(b"a") != t.clone().squeeze_challenge(b"b"));
+    }
+}
@@ -0,0 +1,37 @@
+use substrate_bn::{AffineG1, AffineG2, Fr};
+use wasm_bindgen::prelude::*;
+
+use crate::{commit as commit_values, CommitmentParams, G1Encoding, G2Encoding, HashToCurve};
+
+fn scalar_from_le(bytes: &[u8]) -> Result<Fr, JsError> {
+    let mut be = bytes.to_vec();
+    be.reverse();
+    Fr::from_slice(&be).map_err(|_| JsError::new("scalar is not a canonical element below r"))
+}
+
+/// 64-byte uncompressed point, big-endian x || y
+#[wasm_bindgen]
+pub fn hash_g1(msg: &[u8], dst: &[u8]) -> Result<Vec<u8>, JsError> {
+    let p = AffineG1::try_hash(msg, dst).map_err(|e| JsError::new(&e.to_string()))?;
+    Ok(p.to_uncompressed().to_vec())
+}
+
+/// 128-byte uncompressed point in the EIP-197 layout
+#[wasm_bindgen]
+pub fn hash_g2(msg: &[u8], dst: &[u8]) -> Result<Vec<u8>, JsError> {
+    let p = AffineG2::try_hash(msg, dst).map_err(|e| JsError::new(&e.to_string()))?;
+    Ok(p.to_uncompressed().to_vec())
+}
+
+/// Commitment under `CommitmentParams::new(AffineG1::DST)`, returned as 64 uncompressed bytes.
+/// `scalars_le` holds the values as consecutive 32-byte little-endian scalars
+#[wasm_bindgen]
+pub fn commit(scalars_le: &[u8], blinding_le: &[u8]) -> Result<Vec<u8>, JsError> {
+    if scalars_le.len() % 32 != 0 || blinding_le.len() != 32 {
+        return Err(JsError::new("scalars must be 32 bytes each"));
+    }
+    let values = scalars_le.chunks(32).map(scalar_from_le).collect::<Result<Vec<_>, _>>()?;
+    let r = scalar_from_le(blinding_le)?;
+    let params = CommitmentParams::new(AffineG1::DST).map_err(|e| JsError::new(&e.to_string()))?;
+    Ok(commit_values(values, &params, r).to_uncompressed().to_vec())
+}
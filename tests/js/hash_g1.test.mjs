@@ -0,0 +1,18 @@
+import { test } from "node:test";
+import assert from "node:assert/strict";
+import { createRequire } from "node:module";
+
+const { hash_g1 } = createRequire(import.meta.url)("../../pkg/sp1_hash2curve.js");
+
+const P = 21888242871839275222246405745257275088696311157297823662689037894645226208583n;
+const toBigInt = (bytes) => bytes.reduce((acc, b) => (acc << 8n) | BigInt(b), 0n);
+
+test("hash_g1 returns a 64-byte point on BN254", () => {
+  const out = hash_g1(new TextEncoder().encode("hello"), new TextEncoder().encode("test-dst"));
+  assert.equal(out.length, 64);
+
+  const x = toBigInt(out.slice(0, 32));
+  const y = toBigInt(out.slice(32));
+  assert.ok(x < P && y < P);
+  assert.equal((y * y) % P, (x * x * x + 3n) % P);
+});